@@ -1,17 +1,42 @@
 use std::io::{self, BufRead};
 
 fn main() -> io::Result<()> {
-    println!("Give me a tag-value FIX message, and I will give you a JSON.");
+    // eg: cargo run --example from-stdin -- '555[*].600'
+    let path = match std::env::args()
+        .nth(1)
+        .map(|path| path.parse::<fixparser::FixPath>())
+    {
+        Some(Ok(path)) => Some(path),
+        Some(Err(error)) => {
+            eprintln!("Invalid path: {}", error);
+            std::process::exit(1);
+        }
+        None => None,
+    };
+
+    match path {
+        Some(_) => {
+            println!("Give me a tag-value FIX message, and I will give you the values in the path.")
+        }
+        None => println!("Give me a tag-value FIX message, and I will give you a JSON."),
+    }
     println!("Do you need help? You can try this one: 8=FIX.4.2 | 10=209");
 
     let stdin = io::stdin();
     let handle = stdin.lock();
 
     for line in handle.lines() {
-        if let Some(fix_message) = fixparser::FixMessage::from_tag_value(&line?) {
-            println!("{}", fix_message.to_json());
-        } else {
-            println!("Are your sure you gave me a valid FIX message?");
+        match fixparser::FixMessage::from_tag_value(&line?) {
+            Ok(fix_message) => {
+                for warning in fix_message.warnings() {
+                    eprintln!("WARNING: {}", warning);
+                }
+                match &path {
+                    Some(path) => println!("{}", serde_json::json!(fix_message.query(path))),
+                    None => println!("{}", fix_message.to_json()),
+                }
+            }
+            Err(error) => println!("Are your sure you gave me a valid FIX message? ({})", error),
         }
     }
 
@@ -0,0 +1,23 @@
+use std::io::{self, Read};
+
+fn main() -> io::Result<()> {
+    // eg: cargo run --example from-stream -- capture.bin
+    let reader: Box<dyn Read> = match std::env::args().nth(1) {
+        Some(file_name) => Box::new(std::fs::File::open(file_name)?),
+        None => Box::new(io::stdin()),
+    };
+
+    for fix_message in fixparser::FixStreamParser::new(reader) {
+        match fix_message {
+            Ok(fix_message) => {
+                for warning in fix_message.warnings() {
+                    eprintln!("WARNING: {}", warning);
+                }
+                println!("{}", fix_message.to_json());
+            }
+            Err(error) => eprintln!("ERROR: {}", error),
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,182 @@
+use crate::model::{FixComponent, FixEntity, FixGroup};
+use crate::{tag_value, FixMessage};
+
+/// Builds a message from scratch.
+///
+/// The header fields are placed in the standard order (8, 9, 35, 49, 56, 34, 52) no matter the
+/// order in which they are set, followed by the other fields in the order they are added.
+/// BodyLength (9) and CheckSum (10) are computed when the message is built, so they can't be set.
+///
+/// # Example
+///
+/// ```rust
+/// use fixparser::FixMessageBuilder;
+///
+/// let fix_message = FixMessageBuilder::new("FIX.4.4", "AB")
+///     .sender_comp_id("SENDER")
+///     .target_comp_id("TARGET")
+///     .msg_seq_num(1)
+///     .sending_time("20200520-19:15:45.134")
+///     .field(11, "ORDER1")
+///     .group(555, |legs| {
+///         legs.instance(|leg| {
+///             leg.field(600, "CGY")
+///                 .group(604, |ids| ids.instance(|id| id.field(605, "F7")))
+///         })
+///         .instance(|leg| leg.field(600, "CGZ"))
+///     })
+///     .build();
+///
+/// assert_eq!(vec!["CGY", "CGZ"], fix_message.get_all(600));
+/// println!("{}", fix_message.to_json());
+/// println!("{}", String::from_utf8_lossy(&fix_message.to_tag_value_with(b"|")));
+/// ```
+#[derive(Debug, Clone)]
+pub struct FixMessageBuilder {
+    header: Vec<(i32, Vec<u8>)>,
+    body: ComponentBuilder,
+}
+
+// order of the header fields (BodyLength is added when the message is built)
+const HEADER_TAGS: [i32; 6] = [8, 35, 49, 56, 34, 52];
+
+impl FixMessageBuilder {
+    /// Creates a builder with the given BeginString (tag 8) and MsgType (tag 35).
+    pub fn new(begin_string: impl Into<Vec<u8>>, msg_type: impl Into<Vec<u8>>) -> Self {
+        Self {
+            header: vec![(8, begin_string.into()), (35, msg_type.into())],
+            body: ComponentBuilder::new(),
+        }
+    }
+
+    /// Sets the SenderCompID (tag 49).
+    pub fn sender_comp_id(self, sender_comp_id: impl Into<Vec<u8>>) -> Self {
+        self.header_field(49, sender_comp_id.into())
+    }
+
+    /// Sets the TargetCompID (tag 56).
+    pub fn target_comp_id(self, target_comp_id: impl Into<Vec<u8>>) -> Self {
+        self.header_field(56, target_comp_id.into())
+    }
+
+    /// Sets the MsgSeqNum (tag 34).
+    pub fn msg_seq_num(self, msg_seq_num: u64) -> Self {
+        self.header_field(34, msg_seq_num.to_string().into_bytes())
+    }
+
+    /// Sets the SendingTime (tag 52). eg: `20200520-19:15:45.134`.
+    pub fn sending_time(self, sending_time: impl Into<Vec<u8>>) -> Self {
+        self.header_field(52, sending_time.into())
+    }
+
+    /// Adds a field after the header fields.
+    ///
+    /// The header fields above (8, 35, 49, 56, 34, 52) are set in the header instead (replacing
+    /// their previous value), and BodyLength (9) and CheckSum (10) are ignored.
+    pub fn field(mut self, tag: i32, value: impl Into<Vec<u8>>) -> Self {
+        match tag {
+            9 | 10 => self,
+            tag if HEADER_TAGS.contains(&tag) => self.header_field(tag, value.into()),
+            tag => {
+                self.body = self.body.field(tag, value);
+                self
+            }
+        }
+    }
+
+    /// Adds a repeating group whose NoXXX field has the given tag. Its instances are added with
+    /// the given closure (see [`GroupBuilder::instance`]).
+    pub fn group(mut self, no_tag: i32, build: impl FnOnce(GroupBuilder) -> GroupBuilder) -> Self {
+        self.body = self.body.group(no_tag, build);
+        self
+    }
+
+    /// Creates the message, computing its BodyLength and CheckSum.
+    pub fn build(self) -> FixMessage {
+        let mut header = self.header;
+        header.sort_by_key(|(tag, _value)| {
+            HEADER_TAGS.iter().position(|header_tag| header_tag == tag)
+        });
+
+        let mut root_component = FixComponent::new(
+            header
+                .into_iter()
+                .map(|(tag, value)| FixEntity::Field(tag, value))
+                .chain(self.body.entities)
+                .collect(),
+        );
+        let (body_length, checksum) = tag_value::body_length_and_checksum(&root_component);
+        let entities = &mut root_component.entities;
+        entities.insert(1, FixEntity::Field(9, body_length.to_string().into_bytes()));
+        entities.push(FixEntity::Field(10, checksum.into_bytes()));
+
+        FixMessage {
+            root_component,
+            warnings: Vec::new(),
+            checksum: None,
+            body_length: None,
+        }
+    }
+
+    // sets the value of a header field, replacing the previous one (if any)
+    fn header_field(mut self, tag: i32, value: Vec<u8>) -> Self {
+        match self
+            .header
+            .iter_mut()
+            .find(|(header_tag, _value)| *header_tag == tag)
+        {
+            Some(field) => field.1 = value,
+            None => self.header.push((tag, value)),
+        }
+        self
+    }
+}
+
+/// Builds the instances of a repeating group (see [`FixMessageBuilder::group`]).
+#[derive(Debug, Clone)]
+pub struct GroupBuilder {
+    instances: Vec<FixComponent<Vec<u8>>>,
+}
+
+impl GroupBuilder {
+    /// Adds an instance whose fields and nested groups are added with the given closure.
+    pub fn instance(mut self, build: impl FnOnce(ComponentBuilder) -> ComponentBuilder) -> Self {
+        let instance = build(ComponentBuilder::new());
+        self.instances.push(FixComponent::new(instance.entities));
+        self
+    }
+}
+
+/// Builds the fields and nested groups of an instance of a repeating group (see
+/// [`GroupBuilder::instance`]).
+#[derive(Debug, Clone)]
+pub struct ComponentBuilder {
+    entities: Vec<FixEntity<Vec<u8>>>,
+}
+
+impl ComponentBuilder {
+    fn new() -> Self {
+        Self {
+            entities: Vec::new(),
+        }
+    }
+
+    /// Adds a field.
+    pub fn field(mut self, tag: i32, value: impl Into<Vec<u8>>) -> Self {
+        self.entities.push(FixEntity::Field(tag, value.into()));
+        self
+    }
+
+    /// Adds a nested repeating group whose NoXXX field has the given tag.
+    pub fn group(mut self, no_tag: i32, build: impl FnOnce(GroupBuilder) -> GroupBuilder) -> Self {
+        let group = build(GroupBuilder {
+            instances: Vec::new(),
+        });
+        self.entities
+            .push(FixEntity::Group(FixGroup::from_instances(
+                no_tag,
+                group.instances,
+            )));
+        self
+    }
+}
@@ -0,0 +1,54 @@
+use std::collections::HashMap;
+
+/// Length/data tag pairs defined by the FIX standard.
+///
+/// The value of a data field can contain any byte (including the separator), so its length is
+/// given in the field which precedes it.
+pub const STANDARD_DATA_FIELDS: &[(i32, i32)] = &[
+    (90, 91),     // SecureDataLen, SecureData
+    (93, 89),     // SignatureLength, Signature
+    (95, 96),     // RawDataLength, RawData
+    (212, 213),   // XmlDataLen, XmlData
+    (348, 349),   // EncodedIssuerLen, EncodedIssuer
+    (350, 351),   // EncodedSecurityDescLen, EncodedSecurityDesc
+    (352, 353),   // EncodedListExecInstLen, EncodedListExecInst
+    (354, 355),   // EncodedTextLen, EncodedText
+    (356, 357),   // EncodedSubjectLen, EncodedSubject
+    (358, 359),   // EncodedHeadlineLen, EncodedHeadline
+    (360, 361),   // EncodedAllocTextLen, EncodedAllocText
+    (362, 363),   // EncodedUnderlyingIssuerLen, EncodedUnderlyingIssuer
+    (364, 365),   // EncodedUnderlyingSecurityDescLen, EncodedUnderlyingSecurityDesc
+    (445, 446),   // EncodedListStatusTextLen, EncodedListStatusText
+    (618, 619),   // EncodedLegIssuerLen, EncodedLegIssuer
+    (621, 622),   // EncodedLegSecurityDescLen, EncodedLegSecurityDesc
+    (1184, 1185), // SecurityXMLLen, SecurityXML
+    (1277, 1278), // DerivativeEncodedIssuerLen, DerivativeEncodedIssuer
+    (1401, 1402), // EncryptedPasswordLen, EncryptedPassword
+    (1403, 1404), // EncryptedNewPasswordLen, EncryptedNewPassword
+];
+
+// registry of the length/data tag pairs known by the parser: the STANDARD_DATA_FIELDS and the
+// custom ones (see ParserOptions::data_field)
+#[derive(Debug, Clone, Default)]
+pub(crate) struct DataFields {
+    custom_data_fields: HashMap<i32, i32>,
+}
+
+impl DataFields {
+    pub(crate) fn insert(&mut self, length_tag: i32, data_tag: i32) {
+        self.custom_data_fields.insert(length_tag, data_tag);
+    }
+
+    // data tag whose length is given in length_tag (if any)
+    pub(crate) fn data_tag(&self, length_tag: i32) -> Option<i32> {
+        self.custom_data_fields
+            .get(&length_tag)
+            .copied()
+            .or_else(|| {
+                STANDARD_DATA_FIELDS
+                    .iter()
+                    .find(|(standard_length_tag, _)| *standard_length_tag == length_tag)
+                    .map(|(_, data_tag)| *data_tag)
+            })
+    }
+}
@@ -0,0 +1,434 @@
+use crate::model::{FixComponent, FixEntity, FixGroup};
+use crate::types::{self, Decimal};
+use crate::{ComponentView, DeserializeError};
+use serde::de::{self, DeserializeSeed, IntoDeserializer, MapAccess, SeqAccess, Visitor};
+use serde::forward_to_deserialize_any;
+
+// T is the type of the values (V elsewhere, but forward_to_deserialize_any! uses that name).
+// A component is deserialized as a map whose keys are the tags (as strings, or as numbers if
+// requested) and whose values are the values of the fields or the instances of the groups
+
+impl<'de, T: AsRef<[u8]>> de::Deserializer<'de> for ComponentView<'de, T> {
+    type Error = DeserializeError;
+
+    fn deserialize_any<W: Visitor<'de>>(self, visitor: W) -> Result<W::Value, Self::Error> {
+        ComponentDeserializer(self.entity_slice()).deserialize_any(visitor)
+    }
+
+    fn deserialize_option<W: Visitor<'de>>(self, visitor: W) -> Result<W::Value, Self::Error> {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_newtype_struct<W: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: W,
+    ) -> Result<W::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf unit
+        unit_struct seq tuple tuple_struct map struct enum identifier ignored_any
+    }
+}
+
+struct ComponentDeserializer<'de, T>(&'de [FixEntity<T>]);
+
+impl<'de, T: AsRef<[u8]>> de::Deserializer<'de> for ComponentDeserializer<'de, T> {
+    type Error = DeserializeError;
+
+    fn deserialize_any<W: Visitor<'de>>(self, visitor: W) -> Result<W::Value, Self::Error> {
+        visitor.visit_map(EntitiesAccess {
+            entities: self.0.iter(),
+            value: None,
+        })
+    }
+
+    fn deserialize_option<W: Visitor<'de>>(self, visitor: W) -> Result<W::Value, Self::Error> {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_newtype_struct<W: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: W,
+    ) -> Result<W::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf unit
+        unit_struct seq tuple tuple_struct map struct enum identifier ignored_any
+    }
+}
+
+struct EntitiesAccess<'de, T> {
+    entities: std::slice::Iter<'de, FixEntity<T>>,
+    value: Option<&'de FixEntity<T>>, // entity whose key was the last one deserialized
+}
+
+impl<'de, T: AsRef<[u8]>> MapAccess<'de> for EntitiesAccess<'de, T> {
+    type Error = DeserializeError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Self::Error> {
+        let entity = match self.entities.next() {
+            Some(entity) => entity,
+            None => return Ok(None),
+        };
+        self.value = Some(entity);
+        let tag = match entity {
+            FixEntity::Field(tag, _value) => *tag,
+            FixEntity::Group(group) => group.no_tag,
+        };
+        seed.deserialize(TagDeserializer(tag)).map(Some)
+    }
+
+    fn next_value_seed<S: DeserializeSeed<'de>>(
+        &mut self,
+        seed: S,
+    ) -> Result<S::Value, Self::Error> {
+        match self.value.take() {
+            Some(FixEntity::Field(tag, value)) => seed.deserialize(FieldDeserializer {
+                tag: *tag,
+                value: value.as_ref(),
+                following: &mut self.entities,
+            }),
+            Some(FixEntity::Group(group)) => seed.deserialize(GroupDeserializer(group)),
+            None => Err(de::Error::custom("value requested before its key")),
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.entities.len())
+    }
+}
+
+struct TagDeserializer(i32);
+
+impl<'de> de::Deserializer<'de> for TagDeserializer {
+    type Error = DeserializeError;
+
+    fn deserialize_any<W: Visitor<'de>>(self, visitor: W) -> Result<W::Value, Self::Error> {
+        visitor.visit_string(self.0.to_string())
+    }
+
+    fn deserialize_i64<W: Visitor<'de>>(self, visitor: W) -> Result<W::Value, Self::Error> {
+        visitor.visit_i64(i64::from(self.0))
+    }
+
+    fn deserialize_i8<W: Visitor<'de>>(self, visitor: W) -> Result<W::Value, Self::Error> {
+        self.deserialize_i64(visitor)
+    }
+
+    fn deserialize_i16<W: Visitor<'de>>(self, visitor: W) -> Result<W::Value, Self::Error> {
+        self.deserialize_i64(visitor)
+    }
+
+    fn deserialize_i32<W: Visitor<'de>>(self, visitor: W) -> Result<W::Value, Self::Error> {
+        self.deserialize_i64(visitor)
+    }
+
+    fn deserialize_u8<W: Visitor<'de>>(self, visitor: W) -> Result<W::Value, Self::Error> {
+        self.deserialize_i64(visitor)
+    }
+
+    fn deserialize_u16<W: Visitor<'de>>(self, visitor: W) -> Result<W::Value, Self::Error> {
+        self.deserialize_i64(visitor)
+    }
+
+    fn deserialize_u32<W: Visitor<'de>>(self, visitor: W) -> Result<W::Value, Self::Error> {
+        self.deserialize_i64(visitor)
+    }
+
+    fn deserialize_u64<W: Visitor<'de>>(self, visitor: W) -> Result<W::Value, Self::Error> {
+        self.deserialize_i64(visitor)
+    }
+
+    forward_to_deserialize_any! {
+        bool i128 u128 f32 f64 char str string bytes byte_buf option unit unit_struct
+        newtype_struct seq tuple tuple_struct map struct enum identifier ignored_any
+    }
+}
+
+struct FieldDeserializer<'de, 'a, T> {
+    tag: i32,
+    value: &'de [u8],
+    following: &'a mut std::slice::Iter<'de, FixEntity<T>>, // entities after the field
+}
+
+impl<'de, 'a, T> FieldDeserializer<'de, 'a, T> {
+    fn str(&self) -> Result<&'de str, DeserializeError> {
+        std::str::from_utf8(self.value).map_err(|_| self.invalid_value("a UTF-8 string"))
+    }
+
+    fn parse<P, E>(
+        &self,
+        parse: impl Fn(&str) -> Result<P, E>,
+        expected: &str,
+    ) -> Result<P, DeserializeError> {
+        parse(self.str()?).map_err(|_| self.invalid_value(expected))
+    }
+
+    fn invalid_value(&self, expected: &str) -> DeserializeError {
+        de::Error::custom(format!(
+            "invalid value for tag {} (expected {}): [{}]",
+            self.tag,
+            expected,
+            String::from_utf8_lossy(self.value)
+        ))
+    }
+}
+
+impl<'de, 'a, T: AsRef<[u8]>> de::Deserializer<'de> for FieldDeserializer<'de, 'a, T> {
+    type Error = DeserializeError;
+
+    fn deserialize_any<W: Visitor<'de>>(self, visitor: W) -> Result<W::Value, Self::Error> {
+        match std::str::from_utf8(self.value) {
+            Ok(value) => visitor.visit_borrowed_str(value),
+            Err(_) => visitor.visit_borrowed_bytes(self.value),
+        }
+    }
+
+    fn deserialize_bool<W: Visitor<'de>>(self, visitor: W) -> Result<W::Value, Self::Error> {
+        visitor.visit_bool(self.parse(types::parse_bool, "Y or N")?)
+    }
+
+    fn deserialize_i64<W: Visitor<'de>>(self, visitor: W) -> Result<W::Value, Self::Error> {
+        visitor.visit_i64(self.parse(types::parse_int, "an int")?)
+    }
+
+    fn deserialize_i8<W: Visitor<'de>>(self, visitor: W) -> Result<W::Value, Self::Error> {
+        self.deserialize_i64(visitor)
+    }
+
+    fn deserialize_i16<W: Visitor<'de>>(self, visitor: W) -> Result<W::Value, Self::Error> {
+        self.deserialize_i64(visitor)
+    }
+
+    fn deserialize_i32<W: Visitor<'de>>(self, visitor: W) -> Result<W::Value, Self::Error> {
+        self.deserialize_i64(visitor)
+    }
+
+    fn deserialize_u64<W: Visitor<'de>>(self, visitor: W) -> Result<W::Value, Self::Error> {
+        visitor.visit_u64(self.parse(str::parse::<u64>, "an unsigned int")?)
+    }
+
+    fn deserialize_u8<W: Visitor<'de>>(self, visitor: W) -> Result<W::Value, Self::Error> {
+        self.deserialize_u64(visitor)
+    }
+
+    fn deserialize_u16<W: Visitor<'de>>(self, visitor: W) -> Result<W::Value, Self::Error> {
+        self.deserialize_u64(visitor)
+    }
+
+    fn deserialize_u32<W: Visitor<'de>>(self, visitor: W) -> Result<W::Value, Self::Error> {
+        self.deserialize_u64(visitor)
+    }
+
+    fn deserialize_f64<W: Visitor<'de>>(self, visitor: W) -> Result<W::Value, Self::Error> {
+        // the value is validated as a FIX decimal (eg: no exponent, no "inf")
+        let decimal = self.parse(str::parse::<Decimal>, "a decimal")?;
+        visitor.visit_f64(decimal.to_f64())
+    }
+
+    fn deserialize_f32<W: Visitor<'de>>(self, visitor: W) -> Result<W::Value, Self::Error> {
+        self.deserialize_f64(visitor)
+    }
+
+    fn deserialize_char<W: Visitor<'de>>(self, visitor: W) -> Result<W::Value, Self::Error> {
+        visitor.visit_char(self.parse(types::parse_char, "a char")?)
+    }
+
+    fn deserialize_str<W: Visitor<'de>>(self, visitor: W) -> Result<W::Value, Self::Error> {
+        visitor.visit_borrowed_str(self.str()?)
+    }
+
+    fn deserialize_string<W: Visitor<'de>>(self, visitor: W) -> Result<W::Value, Self::Error> {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_bytes<W: Visitor<'de>>(self, visitor: W) -> Result<W::Value, Self::Error> {
+        visitor.visit_borrowed_bytes(self.value)
+    }
+
+    fn deserialize_byte_buf<W: Visitor<'de>>(self, visitor: W) -> Result<W::Value, Self::Error> {
+        self.deserialize_bytes(visitor)
+    }
+
+    fn deserialize_option<W: Visitor<'de>>(self, visitor: W) -> Result<W::Value, Self::Error> {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_newtype_struct<W: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: W,
+    ) -> Result<W::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    // enums with unit variants (eg: Side), the value being the name of the variant
+    fn deserialize_enum<W: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: W,
+    ) -> Result<W::Value, Self::Error> {
+        visitor.visit_enum(self.str()?.into_deserializer())
+    }
+
+    // a group with a single instance is not detected without a dictionary, so its NoXXX field
+    // is followed by the fields of the instance
+    fn deserialize_seq<W: Visitor<'de>>(self, visitor: W) -> Result<W::Value, Self::Error> {
+        let count = self.parse(str::parse::<usize>, "the number of instances of a group")?;
+        visitor.visit_seq(FlatInstancesAccess {
+            entities: self.following,
+            count,
+            delimiter: None,
+        })
+    }
+
+    forward_to_deserialize_any! {
+        i128 u128 unit unit_struct tuple tuple_struct map struct identifier ignored_any
+    }
+}
+
+struct GroupDeserializer<'de, T>(&'de FixGroup<T>);
+
+impl<'de, T: AsRef<[u8]>> de::Deserializer<'de> for GroupDeserializer<'de, T> {
+    type Error = DeserializeError;
+
+    fn deserialize_any<W: Visitor<'de>>(self, visitor: W) -> Result<W::Value, Self::Error> {
+        visitor.visit_seq(InstancesAccess(self.0.instances.iter()))
+    }
+
+    fn deserialize_option<W: Visitor<'de>>(self, visitor: W) -> Result<W::Value, Self::Error> {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_newtype_struct<W: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: W,
+    ) -> Result<W::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf unit
+        unit_struct seq tuple tuple_struct map struct enum identifier ignored_any
+    }
+}
+
+struct InstancesAccess<'de, T>(std::slice::Iter<'de, FixComponent<T>>);
+
+impl<'de, T: AsRef<[u8]>> SeqAccess<'de> for InstancesAccess<'de, T> {
+    type Error = DeserializeError;
+
+    fn next_element_seed<S: DeserializeSeed<'de>>(
+        &mut self,
+        seed: S,
+    ) -> Result<Option<S::Value>, Self::Error> {
+        self.0
+            .next()
+            .map(|instance| seed.deserialize(ComponentDeserializer(&instance.entities)))
+            .transpose()
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.0.len())
+    }
+}
+
+// instances of a group which was not detected (ie: their fields follow the NoXXX field)
+struct FlatInstancesAccess<'de, 'a, T> {
+    entities: &'a mut std::slice::Iter<'de, FixEntity<T>>,
+    count: usize,           // instances left, according to the NoXXX field
+    delimiter: Option<i32>, // first tag of the first instance
+}
+
+impl<'de, 'a, T: AsRef<[u8]>> SeqAccess<'de> for FlatInstancesAccess<'de, 'a, T> {
+    type Error = DeserializeError;
+
+    fn next_element_seed<S: DeserializeSeed<'de>>(
+        &mut self,
+        seed: S,
+    ) -> Result<Option<S::Value>, Self::Error> {
+        let next_tag = self.entities.as_slice().first().map(FixEntity::tag);
+        // there are less instances than declared if the delimiter is not next
+        let first_instance = self.delimiter.is_none();
+        if self.count == 0 || (!first_instance && next_tag != self.delimiter) {
+            return Ok(None);
+        }
+        self.count -= 1;
+        let value = seed.deserialize(FlatInstanceDeserializer(&mut *self))?;
+        if first_instance && self.delimiter.is_none() {
+            self.count = 0; // the first instance has no fields
+        }
+        Ok(Some(value))
+    }
+}
+
+struct FlatInstanceDeserializer<'de, 'a, 'b, T>(&'b mut FlatInstancesAccess<'de, 'a, T>);
+
+impl<'de, 'a, 'b, T: AsRef<[u8]>> de::Deserializer<'de>
+    for FlatInstanceDeserializer<'de, 'a, 'b, T>
+{
+    type Error = DeserializeError;
+
+    fn deserialize_any<W: Visitor<'de>>(self, _visitor: W) -> Result<W::Value, Self::Error> {
+        Err(de::Error::custom(
+            "a group which was not detected can only be deserialized into structs",
+        ))
+    }
+
+    // the instance is made of the next fields which are members of the struct (the delimiter
+    // starts the next instance)
+    fn deserialize_struct<W: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        visitor: W,
+    ) -> Result<W::Value, Self::Error> {
+        let entities = self.0.entities.as_slice();
+        let delimiter = self
+            .0
+            .delimiter
+            .or_else(|| entities.first().map(FixEntity::tag));
+        let length = entities
+            .iter()
+            .enumerate()
+            .take_while(|(index, entity)| {
+                let tag = entity.tag();
+                (*index == 0 || Some(tag) != delimiter) && fields.contains(&&*tag.to_string())
+            })
+            .count();
+        let (instance, following) = entities.split_at(length);
+        *self.0.entities = following.iter();
+        if length > 0 {
+            self.0.delimiter = delimiter;
+        }
+        ComponentDeserializer(instance).deserialize_any(visitor)
+    }
+
+    fn deserialize_option<W: Visitor<'de>>(self, visitor: W) -> Result<W::Value, Self::Error> {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_newtype_struct<W: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: W,
+    ) -> Result<W::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf unit
+        unit_struct seq tuple tuple_struct map enum identifier ignored_any
+    }
+}
@@ -0,0 +1,124 @@
+use std::fmt;
+
+/// Kind of anomaly found in a message which could be parsed anyway.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WarningKind {
+    /// A token without `=` was ignored.
+    IgnoredToken,
+    /// A token whose tag is not a number was ignored together with its value.
+    InvalidTag,
+    /// A field has no value.
+    EmptyValue,
+    /// Characters found after the 3 digits of the checksum were ignored.
+    CharactersAfterChecksum,
+    /// A field was found after tag 10. Everything from this field on was ignored.
+    TagAfterChecksum,
+    /// The message has no tag 10.
+    MissingChecksum,
+    /// The message ended before all the repetitions of a group were found.
+    EndOfMessageInGroup,
+    /// The value of tag 10 does not match the checksum computed over the message.
+    ChecksumMismatch { declared: String, computed: String },
+    /// The value of tag 9 does not match the length of the body of the message.
+    BodyLengthMismatch { declared: String, computed: usize },
+    /// The value of a data field does not have the length given in the previous field, so it was
+    /// parsed as a regular field.
+    InvalidDataLength { length: usize },
+    /// The tag found where BeginString (8), BodyLength (9) or MsgType (35) was expected (they
+    /// must be the first three tags). Only checked with `ParserOptions::require_standard_header`.
+    MisplacedHeaderTag { expected: i32 },
+    /// A tag was repeated in the same component, or in more instances of a group than the NoXXX
+    /// field declares. A repeated tag is parsed as a group whose NoXXX field is the one before its
+    /// first occurrence, so it is only kept as a duplicate if there is no field before it.
+    DuplicateTag,
+    /// The NoXXX field of a group is not a number of repetitions, so the group has none (its
+    /// instances are reported as duplicates). The tag is the NoXXX one.
+    InvalidGroupCount { value: String },
+    /// A repeated tag has no field before its first occurrence which could be the NoXXX one, so it
+    /// was kept as a duplicate.
+    MissingGroupCount,
+}
+
+/// A warning about something unexpected found while parsing a message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseWarning {
+    pub kind: WarningKind,
+    /// Tag of the field (if known). For `EndOfMessageInGroup`, it is the NoXXX tag of the group.
+    pub tag: Option<i32>,
+    /// Byte offset (relative to the beginning of the input) of the token.
+    pub offset: usize,
+    /// The raw token as found in the input (invalid UTF-8 sequences are replaced with `U+FFFD`).
+    pub token: String,
+}
+
+impl ParseWarning {
+    pub(crate) fn new(kind: WarningKind, tag: Option<i32>, offset: usize, token: &[u8]) -> Self {
+        Self {
+            kind,
+            tag,
+            offset,
+            token: String::from_utf8_lossy(token).into_owned(),
+        }
+    }
+}
+
+impl fmt::Display for ParseWarning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.kind {
+            WarningKind::IgnoredToken | WarningKind::InvalidTag => {
+                write!(f, "Ignoring [{}]", self.token)
+            }
+            WarningKind::EmptyValue => {
+                write!(f, "Tag {} has no value", self.tag.unwrap_or_default())
+            }
+            WarningKind::CharactersAfterChecksum => {
+                write!(f, "Ignoring characters after checksum [{}]", self.token)
+            }
+            WarningKind::TagAfterChecksum => {
+                write!(f, "Detected tag after tag 10: [{}]", self.token)
+            }
+            WarningKind::MissingChecksum => write!(f, "Message is incomplete (missing tag 10)"),
+            WarningKind::EndOfMessageInGroup => write!(
+                f,
+                "End of message detected while parsing group {}",
+                self.tag.unwrap_or_default()
+            ),
+            WarningKind::ChecksumMismatch { declared, computed } => write!(
+                f,
+                "Wrong checksum: declared {}, computed {}",
+                declared, computed
+            ),
+            WarningKind::InvalidDataLength { length } => write!(
+                f,
+                "Data field {} does not have the expected length ({})",
+                self.tag.unwrap_or_default(),
+                length
+            ),
+            WarningKind::BodyLengthMismatch { declared, computed } => write!(
+                f,
+                "Wrong body length: declared {}, computed {}",
+                declared, computed
+            ),
+            WarningKind::MisplacedHeaderTag { expected } => {
+                write!(f, "Expected tag {}, found [{}]", expected, self.token)
+            }
+            WarningKind::DuplicateTag => write!(
+                f,
+                "Tag {} is repeated [{}]",
+                self.tag.unwrap_or_default(),
+                self.token
+            ),
+            WarningKind::InvalidGroupCount { value } => write!(
+                f,
+                "Invalid number of repetitions for group {}: [{}]",
+                self.tag.unwrap_or_default(),
+                value
+            ),
+            WarningKind::MissingGroupCount => write!(
+                f,
+                "Repeating group with delimiter {} has no NoXXX field",
+                self.tag.unwrap_or_default()
+            ),
+        }
+    }
+}
@@ -0,0 +1,350 @@
+#[cfg(feature = "dictionary")]
+mod orchestra;
+#[cfg(feature = "dictionary")]
+mod quickfix;
+
+#[cfg(feature = "dictionary")]
+use crate::DictionaryError;
+#[cfg(feature = "dictionary")]
+use roxmltree::{Document, Node};
+use std::collections::{BTreeMap, HashMap};
+#[cfg(feature = "dictionary")]
+use std::path::Path;
+
+/// A FIX data dictionary: definitions of fields, components, repeating groups and messages.
+///
+/// The parser doesn't need it, but it can be used to interpret the messages (eg: names of the
+/// tags, meaning of the values, required fields).
+///
+/// # Example
+///
+/// ```rust
+/// # #[cfg(feature = "dictionary")]
+/// # fn main() {
+/// let xml = r#"
+/// <fix type="FIX" major="4" minor="4" servicepack="0">
+///   <header><field name="BeginString" required="Y"/></header>
+///   <trailer><field name="CheckSum" required="Y"/></trailer>
+///   <messages>
+///     <message name="Heartbeat" msgtype="0" msgcat="admin">
+///       <field name="TestReqID" required="N"/>
+///     </message>
+///   </messages>
+///   <components/>
+///   <fields>
+///     <field number="8" name="BeginString" type="STRING"/>
+///     <field number="10" name="CheckSum" type="STRING"/>
+///     <field number="112" name="TestReqID" type="STRING"/>
+///   </fields>
+/// </fix>"#;
+/// let dictionary = fixparser::Dictionary::from_quickfix_xml(xml).unwrap();
+/// assert_eq!("FIX.4.4", dictionary.version());
+/// assert_eq!("TestReqID", dictionary.field(112).unwrap().name);
+/// assert_eq!("Heartbeat", dictionary.message("0").unwrap().name);
+/// # }
+/// # #[cfg(not(feature = "dictionary"))]
+/// # fn main() {}
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Dictionary {
+    pub(crate) version: String,
+    pub(crate) fields: BTreeMap<i32, FieldDef>,
+    pub(crate) tags_by_name: HashMap<String, i32>,
+    pub(crate) header: ComponentDef,
+    pub(crate) trailer: ComponentDef,
+    pub(crate) components: BTreeMap<String, ComponentDef>,
+    pub(crate) messages: BTreeMap<String, MessageDef>, // by MsgType
+}
+
+// the loaders need an xml parser, which is optional
+#[cfg(feature = "dictionary")]
+impl Dictionary {
+    /// Loads a dictionary in the format used by QuickFIX (eg: `FIX44.xml`, `FIXT11.xml`).
+    pub fn from_quickfix_xml(xml: &str) -> Result<Dictionary, DictionaryError> {
+        quickfix::load(xml)
+    }
+
+    /// Same as [`Dictionary::from_quickfix_xml`], but the dictionary is read from a file.
+    pub fn from_quickfix_file(path: impl AsRef<Path>) -> Result<Dictionary, DictionaryError> {
+        Self::from_quickfix_xml(&read_file(path.as_ref())?)
+    }
+
+    /// Loads a dictionary from a [FIX Orchestra](https://www.fixtrading.org/standards/fix-orchestra/)
+    /// repository (eg: `repository.xml`).
+    ///
+    /// Only the base scenario of each element is loaded. The header and the trailer are the
+    /// components named `StandardHeader` and `StandardTrailer`, and they are not included in
+    /// the components nor in the members of the messages. The type of a field whose values are
+    /// enumerated in a code set is the type of the code set (eg: `char`), and the description of
+    /// each value is the name of its code (eg: `Buy`).
+    pub fn from_orchestra_xml(xml: &str) -> Result<Dictionary, DictionaryError> {
+        orchestra::load(xml)
+    }
+
+    /// Same as [`Dictionary::from_orchestra_xml`], but the repository is read from a file.
+    pub fn from_orchestra_file(path: impl AsRef<Path>) -> Result<Dictionary, DictionaryError> {
+        Self::from_orchestra_xml(&read_file(path.as_ref())?)
+    }
+}
+
+impl Dictionary {
+    /// Version of FIX described by the dictionary (eg: `FIX.4.4`, `FIXT.1.1`, `FIX.5.0SP2`).
+    pub fn version(&self) -> &str {
+        &self.version
+    }
+
+    /// Get the definition of the field with the given tag.
+    pub fn field(&self, tag: i32) -> Option<&FieldDef> {
+        self.fields.get(&tag)
+    }
+
+    /// Get the definition of the field with the given name (eg: `ClOrdID`).
+    pub fn field_by_name(&self, name: &str) -> Option<&FieldDef> {
+        self.tags_by_name.get(name).and_then(|tag| self.field(*tag))
+    }
+
+    /// Iterates over the definitions of the fields, sorted by tag.
+    pub fn fields(&self) -> impl Iterator<Item = &FieldDef> {
+        self.fields.values()
+    }
+
+    /// Get the definition of the component with the given name (eg: `Instrument`).
+    pub fn component(&self, name: &str) -> Option<&ComponentDef> {
+        self.components.get(name)
+    }
+
+    /// Iterates over the definitions of the components, sorted by name.
+    pub fn components(&self) -> impl Iterator<Item = &ComponentDef> {
+        self.components.values()
+    }
+
+    /// Get the definition of the message with the given MsgType (eg: `D` for NewOrderSingle).
+    pub fn message(&self, msg_type: &str) -> Option<&MessageDef> {
+        self.messages.get(msg_type)
+    }
+
+    /// Iterates over the definitions of the messages, sorted by MsgType.
+    pub fn messages(&self) -> impl Iterator<Item = &MessageDef> {
+        self.messages.values()
+    }
+
+    /// Fields and groups of the standard header.
+    pub fn header(&self) -> &ComponentDef {
+        &self.header
+    }
+
+    /// Fields of the standard trailer.
+    pub fn trailer(&self) -> &ComponentDef {
+        &self.trailer
+    }
+}
+
+#[cfg(feature = "dictionary")]
+pub(crate) fn read_file(path: &Path) -> Result<String, DictionaryError> {
+    std::fs::read_to_string(path).map_err(|error| DictionaryError::Io {
+        message: format!("{}: {}", path.display(), error),
+    })
+}
+
+/// Definition of a field.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldDef {
+    pub tag: i32,
+    pub name: String,
+    /// Type of the values as named in the dictionary (eg: `STRING`, `PRICE`, `NUMINGROUP`).
+    pub field_type: String,
+    /// Valid values (if they are enumerated).
+    pub values: Vec<FieldValue>,
+}
+
+impl FieldDef {
+    /// Get the description of the given value (eg: `BUY` for value `1` of Side).
+    pub fn value_description(&self, value: &str) -> Option<&str> {
+        self.values
+            .iter()
+            .find(|field_value| field_value.value == value)
+            .map(|field_value| field_value.description.as_str())
+    }
+}
+
+/// An enumerated value of a field.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldValue {
+    pub value: String,
+    pub description: String,
+}
+
+/// A field, component or repeating group which is part of a message, component or group.
+///
+/// The members of the components are included in the definition, so a message can be walked
+/// without looking the components up.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Member {
+    Field {
+        tag: i32,
+        required: bool,
+    },
+    Component {
+        name: String,
+        required: bool,
+        members: Vec<Member>,
+    },
+    Group(GroupDef),
+}
+
+impl Member {
+    pub fn is_required(&self) -> bool {
+        match self {
+            Member::Field { required, .. } | Member::Component { required, .. } => *required,
+            Member::Group(group) => group.required,
+        }
+    }
+}
+
+/// Definition of a repeating group.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GroupDef {
+    /// Name of the NoXXX field (eg: `NoPartyIDs`).
+    pub name: String,
+    /// Tag of the NoXXX field.
+    pub no_tag: i32,
+    pub required: bool,
+    /// First tag of each instance.
+    pub delimiter: i32,
+    pub members: Vec<Member>,
+}
+
+impl GroupDef {
+    /// Tags which can be found in an instance of the group: tags of its fields (including the
+    /// ones in its components) and NoXXX tags of its nested groups.
+    pub fn member_tags(&self) -> Vec<i32> {
+        let mut tags = Vec::new();
+        collect_member_tags(&self.members, &mut tags);
+        tags
+    }
+
+    // same as member_tags().contains(&tag) without collecting the tags
+    pub(crate) fn has_member_tag(&self, tag: i32) -> bool {
+        has_member_tag(&self.members, tag)
+    }
+
+    /// Get the definition of a nested group (see [`MessageDef::group`]).
+    pub fn group(&self, no_tag: i32) -> Option<&GroupDef> {
+        find_group(&self.members, no_tag)
+    }
+}
+
+/// Definition of a component (a reusable set of fields and groups).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ComponentDef {
+    pub name: String,
+    pub members: Vec<Member>,
+}
+
+impl ComponentDef {
+    /// Get the definition of a group of the component (see [`MessageDef::group`]).
+    pub fn group(&self, no_tag: i32) -> Option<&GroupDef> {
+        find_group(&self.members, no_tag)
+    }
+}
+
+/// Definition of a message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MessageDef {
+    pub name: String,
+    pub msg_type: String,
+    /// Category of the message (eg: `admin`, `app`).
+    pub category: String,
+    /// Fields, components and groups of the body (ie: without the header and the trailer).
+    pub members: Vec<Member>,
+}
+
+impl MessageDef {
+    /// Get the definition of a group of the message, given the tag of its NoXXX field.
+    ///
+    /// Groups inside components are looked up, but nested groups are looked up from their
+    /// parent group (see [`GroupDef::group`]).
+    pub fn group(&self, no_tag: i32) -> Option<&GroupDef> {
+        find_group(&self.members, no_tag)
+    }
+}
+
+pub(crate) fn find_group(members: &[Member], no_tag: i32) -> Option<&GroupDef> {
+    members.iter().find_map(|member| match member {
+        Member::Group(group) if group.no_tag == no_tag => Some(group),
+        Member::Component { members, .. } => find_group(members, no_tag),
+        _ => None,
+    })
+}
+
+fn collect_member_tags(members: &[Member], tags: &mut Vec<i32>) {
+    for member in members {
+        match member {
+            Member::Field { tag, .. } => tags.push(*tag),
+            Member::Component { members, .. } => collect_member_tags(members, tags),
+            Member::Group(group) => tags.push(group.no_tag),
+        }
+    }
+}
+
+pub(crate) fn has_member_tag(members: &[Member], tag: i32) -> bool {
+    members.iter().any(|member| match member {
+        Member::Field {
+            tag: member_tag, ..
+        } => *member_tag == tag,
+        Member::Component { members, .. } => has_member_tag(members, tag),
+        Member::Group(group) => group.no_tag == tag,
+    })
+}
+
+// first tag of the given members (ie: the delimiter of a group with such members)
+#[cfg(feature = "dictionary")]
+pub(crate) fn first_tag(members: &[Member]) -> Option<i32> {
+    members.first().and_then(|member| match member {
+        Member::Field { tag, .. } => Some(*tag),
+        Member::Component { members, .. } => first_tag(members),
+        Member::Group(group) => Some(group.no_tag),
+    })
+}
+
+// parses the xml of a dictionary checking the name of its root element (without namespace)
+#[cfg(feature = "dictionary")]
+pub(crate) fn parse_xml<'x>(
+    xml: &'x str,
+    root_name: &str,
+) -> Result<Document<'x>, DictionaryError> {
+    let document = Document::parse(xml).map_err(|error| DictionaryError::Xml {
+        message: error.to_string(),
+    })?;
+    if document.root_element().tag_name().name() != root_name {
+        return Err(DictionaryError::Xml {
+            message: format!("the root element is not <{}>", root_name),
+        });
+    }
+    Ok(document)
+}
+
+#[cfg(feature = "dictionary")]
+pub(crate) fn child<'x, 'i>(node: Node<'x, 'i>, name: &str) -> Option<Node<'x, 'i>> {
+    node.children()
+        .find(|child| child.tag_name().name() == name)
+}
+
+#[cfg(feature = "dictionary")]
+pub(crate) fn elements<'x, 'i: 'x>(
+    node: Option<Node<'x, 'i>>,
+    name: &'x str,
+) -> impl Iterator<Item = Node<'x, 'i>> + 'x {
+    node.into_iter()
+        .flat_map(|node| node.children())
+        .filter(move |child| child.tag_name().name() == name)
+}
+
+#[cfg(feature = "dictionary")]
+pub(crate) fn attribute<'x>(node: Node<'x, '_>, name: &str) -> Result<&'x str, DictionaryError> {
+    node.attribute(name)
+        .ok_or_else(|| DictionaryError::MissingAttribute {
+            element: node.tag_name().name().to_string(),
+            attribute: name.to_string(),
+        })
+}
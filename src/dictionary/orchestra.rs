@@ -0,0 +1,246 @@
+use super::{
+    attribute, child, elements, first_tag, parse_xml, ComponentDef, Dictionary, FieldDef,
+    FieldValue, GroupDef, Member, MessageDef,
+};
+use crate::DictionaryError;
+use roxmltree::{Node, NodeId};
+use std::collections::{BTreeMap, HashMap};
+
+const HEADER: &str = "StandardHeader";
+const TRAILER: &str = "StandardTrailer";
+
+// loads a dictionary in FIX Orchestra format (ie: a repository):
+//
+// <fixr:repository name="" version="FIX.4.4">
+//   <fixr:codeSets><fixr:codeSet name="" type=""><fixr:code name="" value=""/>...</fixr:codeSet>...</fixr:codeSets>
+//   <fixr:fields><fixr:field id="" name="" type=""/>...</fixr:fields>
+//   <fixr:components><fixr:component id="" name="">REFS</fixr:component>...</fixr:components>
+//   <fixr:groups><fixr:group id="" name=""><fixr:numInGroup id=""/>REFS</fixr:group>...</fixr:groups>
+//   <fixr:messages><fixr:message name="" msgType="" category=""><fixr:structure>REFS</fixr:structure></fixr:message>...</fixr:messages>
+// </fixr:repository>
+//
+// where REFS are <fixr:fieldRef id="" presence=""/>, <fixr:componentRef id="" presence=""/> and
+// <fixr:groupRef id="" presence=""/>. The type of a field can be the name of a code set.
+// Elements of other scenarios than the base one are ignored (eg: <fixr:message scenario="">)
+pub(crate) fn load(xml: &str) -> Result<Dictionary, DictionaryError> {
+    let document = parse_xml(xml, "repository")?;
+    let root = document.root_element();
+
+    let fields = load_fields(root)?;
+    let mut loader = Loader {
+        fields: &fields,
+        component_nodes: base_elements(child(root, "components"), "component")
+            .map(|node| Ok((attribute(node, "id")?, node)))
+            .collect::<Result<_, DictionaryError>>()?,
+        group_nodes: base_elements(child(root, "groups"), "group")
+            .map(|node| Ok((attribute(node, "id")?, node)))
+            .collect::<Result<_, DictionaryError>>()?,
+        loaded: HashMap::new(),
+        loading: Vec::new(),
+    };
+
+    let mut components = BTreeMap::new();
+    let nodes: Vec<Node> = loader.component_nodes.values().copied().collect();
+    for node in nodes {
+        let name = attribute(node, "name")?.to_string();
+        let members = loader.load(node)?;
+        components.insert(name.clone(), ComponentDef { name, members });
+    }
+    let header = components
+        .remove(HEADER)
+        .unwrap_or_else(|| empty_component(HEADER));
+    let trailer = components
+        .remove(TRAILER)
+        .unwrap_or_else(|| empty_component(TRAILER));
+
+    let mut messages = BTreeMap::new();
+    for node in base_elements(child(root, "messages"), "message") {
+        let message = MessageDef {
+            name: attribute(node, "name")?.to_string(),
+            msg_type: attribute(node, "msgType")?.to_string(),
+            category: node.attribute("category").unwrap_or_default().to_string(),
+            members: loader
+                .members(child(node, "structure"))?
+                .into_iter()
+                .filter(|member| !is_header_or_trailer(member))
+                .collect(),
+        };
+        messages.insert(message.msg_type.clone(), message);
+    }
+
+    let version = root
+        .attribute("version")
+        .map_or_else(|| attribute(root, "name"), Ok)?
+        .to_string();
+    Ok(Dictionary {
+        version,
+        tags_by_name: fields
+            .values()
+            .map(|field| (field.name.clone(), field.tag))
+            .collect(),
+        fields,
+        header,
+        trailer,
+        components,
+        messages,
+    })
+}
+
+fn empty_component(name: &str) -> ComponentDef {
+    ComponentDef {
+        name: name.to_string(),
+        members: Vec::new(),
+    }
+}
+
+fn is_header_or_trailer(member: &Member) -> bool {
+    match member {
+        Member::Component { name, .. } => name == HEADER || name == TRAILER,
+        _ => false,
+    }
+}
+
+fn load_fields(root: Node) -> Result<BTreeMap<i32, FieldDef>, DictionaryError> {
+    let code_sets = base_elements(child(root, "codeSets"), "codeSet")
+        .map(|node| Ok((attribute(node, "name")?, node)))
+        .collect::<Result<HashMap<_, _>, DictionaryError>>()?;
+
+    let mut fields = BTreeMap::new();
+    for node in base_elements(child(root, "fields"), "field") {
+        let tag = tag(attribute(node, "id")?)?;
+        let mut field_type = attribute(node, "type")?;
+        let mut values = Vec::new();
+        if let Some(code_set) = code_sets.get(field_type) {
+            field_type = attribute(*code_set, "type")?;
+            for code in base_elements(Some(*code_set), "code") {
+                values.push(FieldValue {
+                    value: attribute(code, "value")?.to_string(),
+                    description: attribute(code, "name")?.to_string(),
+                });
+            }
+        }
+        let field = FieldDef {
+            tag,
+            name: attribute(node, "name")?.to_string(),
+            field_type: field_type.to_string(),
+            values,
+        };
+        fields.insert(tag, field);
+    }
+    Ok(fields)
+}
+
+struct Loader<'f, 'x, 'i> {
+    fields: &'f BTreeMap<i32, FieldDef>,
+    component_nodes: HashMap<&'x str, Node<'x, 'i>>, // by id
+    group_nodes: HashMap<&'x str, Node<'x, 'i>>,     // by id
+    loaded: HashMap<NodeId, Vec<Member>>, // members of the components and groups already loaded
+    loading: Vec<NodeId>,                 // components and groups being loaded (to detect cycles)
+}
+
+impl<'f, 'x, 'i> Loader<'f, 'x, 'i> {
+    fn members(&mut self, node: Option<Node<'x, 'i>>) -> Result<Vec<Member>, DictionaryError> {
+        let mut members = Vec::new();
+        for node in node.into_iter().flat_map(|node| node.children()) {
+            let presence = node.attribute("presence").unwrap_or("optional");
+            if presence == "forbidden" {
+                continue;
+            }
+            let required = presence == "required";
+            let member = match node.tag_name().name() {
+                "fieldRef" => Member::Field {
+                    tag: self.field(attribute(node, "id")?)?.tag,
+                    required,
+                },
+                "componentRef" => self.component(attribute(node, "id")?, required)?,
+                "groupRef" => self.group(attribute(node, "id")?, required)?,
+                _ => continue, // eg: numInGroup, annotation, text
+            };
+            members.push(member);
+        }
+        Ok(members)
+    }
+
+    fn component(&mut self, id: &str, required: bool) -> Result<Member, DictionaryError> {
+        let node =
+            *self
+                .component_nodes
+                .get(id)
+                .ok_or_else(|| DictionaryError::UnknownComponent {
+                    name: id.to_string(),
+                })?;
+        Ok(Member::Component {
+            name: attribute(node, "name")?.to_string(),
+            required,
+            members: self.load(node)?,
+        })
+    }
+
+    fn group(&mut self, id: &str, required: bool) -> Result<Member, DictionaryError> {
+        let node = *self
+            .group_nodes
+            .get(id)
+            .ok_or_else(|| DictionaryError::UnknownGroup {
+                name: id.to_string(),
+            })?;
+        let no_field = match child(node, "numInGroup") {
+            Some(num_in_group) => self.field(attribute(num_in_group, "id")?)?,
+            None => {
+                return Err(DictionaryError::Xml {
+                    message: format!("group [{}] has no <numInGroup>", id),
+                })
+            }
+        };
+        let members = self.load(node)?;
+        Ok(Member::Group(GroupDef {
+            name: no_field.name.clone(),
+            no_tag: no_field.tag,
+            required,
+            delimiter: first_tag(&members).ok_or_else(|| DictionaryError::EmptyGroup {
+                name: no_field.name.clone(),
+            })?,
+            members,
+        }))
+    }
+
+    // members of a component or a group
+    fn load(&mut self, node: Node<'x, 'i>) -> Result<Vec<Member>, DictionaryError> {
+        if let Some(members) = self.loaded.get(&node.id()) {
+            return Ok(members.clone());
+        }
+        if self.loading.contains(&node.id()) {
+            return Err(DictionaryError::RecursiveComponent {
+                name: attribute(node, "name")?.to_string(),
+            });
+        }
+
+        self.loading.push(node.id());
+        let members = self.members(Some(node));
+        self.loading.pop();
+        let members = members?;
+        self.loaded.insert(node.id(), members.clone());
+        Ok(members)
+    }
+
+    fn field(&self, id: &str) -> Result<&'f FieldDef, DictionaryError> {
+        self.fields
+            .get(&tag(id)?)
+            .ok_or_else(|| DictionaryError::UnknownField {
+                name: id.to_string(),
+            })
+    }
+}
+
+fn tag(id: &str) -> Result<i32, DictionaryError> {
+    id.parse().map_err(|_| DictionaryError::InvalidTag {
+        value: id.to_string(),
+    })
+}
+
+// elements of the base scenario (ie: the ones without a scenario or with the scenario "base")
+fn base_elements<'x, 'i: 'x>(
+    node: Option<Node<'x, 'i>>,
+    name: &'x str,
+) -> impl Iterator<Item = Node<'x, 'i>> + 'x {
+    elements(node, name).filter(|node| node.attribute("scenario").unwrap_or("base") == "base")
+}
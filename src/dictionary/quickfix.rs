@@ -0,0 +1,203 @@
+use super::{
+    attribute, child, elements, first_tag, parse_xml, ComponentDef, Dictionary, FieldDef,
+    FieldValue, GroupDef, Member, MessageDef,
+};
+use crate::DictionaryError;
+use roxmltree::Node;
+use std::collections::{BTreeMap, HashMap};
+
+// loads a dictionary in QuickFIX format:
+//
+// <fix type="FIX" major="4" minor="4" servicepack="0">
+//   <header>MEMBERS</header> <trailer>MEMBERS</trailer>
+//   <messages><message name="" msgtype="" msgcat="">MEMBERS</message>...</messages>
+//   <components><component name="">MEMBERS</component>...</components>
+//   <fields><field number="" name="" type=""><value enum="" description=""/>...</field>...</fields>
+// </fix>
+//
+// where MEMBERS are <field name="" required=""/>, <component name="" required=""/> and
+// <group name="" required="">MEMBERS</group>
+pub(crate) fn load(xml: &str) -> Result<Dictionary, DictionaryError> {
+    let document = parse_xml(xml, "fix")?;
+    let root = document.root_element();
+
+    let fields = load_fields(root)?;
+    let mut loader = Loader {
+        tags_by_name: fields
+            .values()
+            .map(|field| (field.name.clone(), field.tag))
+            .collect(),
+        component_nodes: elements(child(root, "components"), "component")
+            .map(|node| Ok((attribute(node, "name")?, node)))
+            .collect::<Result<_, DictionaryError>>()?,
+        components: HashMap::new(),
+        loading: Vec::new(),
+    };
+
+    let header = ComponentDef {
+        name: String::from("Header"),
+        members: loader.members(child(root, "header"))?,
+    };
+    let trailer = ComponentDef {
+        name: String::from("Trailer"),
+        members: loader.members(child(root, "trailer"))?,
+    };
+
+    let mut messages = BTreeMap::new();
+    for node in elements(child(root, "messages"), "message") {
+        let message = MessageDef {
+            name: attribute(node, "name")?.to_string(),
+            msg_type: attribute(node, "msgtype")?.to_string(),
+            category: node.attribute("msgcat").unwrap_or_default().to_string(),
+            members: loader.members(Some(node))?,
+        };
+        messages.insert(message.msg_type.clone(), message);
+    }
+
+    let mut components = BTreeMap::new();
+    let names: Vec<&str> = loader.component_nodes.keys().copied().collect();
+    for name in names {
+        let members = loader.component_members(name)?;
+        let name = name.to_string();
+        components.insert(name.clone(), ComponentDef { name, members });
+    }
+
+    Ok(Dictionary {
+        version: version(root),
+        tags_by_name: loader.tags_by_name,
+        fields,
+        header,
+        trailer,
+        components,
+        messages,
+    })
+}
+
+// eg: FIX.4.4, FIXT.1.1, FIX.5.0SP2
+fn version(root: Node) -> String {
+    let mut version = format!(
+        "{}.{}.{}",
+        root.attribute("type").unwrap_or("FIX"),
+        root.attribute("major").unwrap_or_default(),
+        root.attribute("minor").unwrap_or_default()
+    );
+    match root.attribute("servicepack") {
+        Some(service_pack) if !service_pack.is_empty() && service_pack != "0" => {
+            version.push_str("SP");
+            version.push_str(service_pack);
+        }
+        _ => {}
+    }
+    version
+}
+
+fn load_fields(root: Node) -> Result<BTreeMap<i32, FieldDef>, DictionaryError> {
+    let mut fields = BTreeMap::new();
+    for node in elements(child(root, "fields"), "field") {
+        let number = attribute(node, "number")?;
+        let tag = number.parse().map_err(|_| DictionaryError::InvalidTag {
+            value: number.to_string(),
+        })?;
+        let values = elements(Some(node), "value")
+            .map(|value| {
+                Ok(FieldValue {
+                    value: attribute(value, "enum")?.to_string(),
+                    description: value
+                        .attribute("description")
+                        .unwrap_or_default()
+                        .to_string(),
+                })
+            })
+            .collect::<Result<_, DictionaryError>>()?;
+        let field = FieldDef {
+            tag,
+            name: attribute(node, "name")?.to_string(),
+            field_type: attribute(node, "type")?.to_string(),
+            values,
+        };
+        fields.insert(tag, field);
+    }
+    Ok(fields)
+}
+
+struct Loader<'x, 'i> {
+    tags_by_name: HashMap<String, i32>,
+    component_nodes: HashMap<&'x str, Node<'x, 'i>>,
+    components: HashMap<&'x str, Vec<Member>>, // members of the components already loaded
+    loading: Vec<&'x str>,                     // components being loaded (to detect cycles)
+}
+
+impl<'x, 'i> Loader<'x, 'i> {
+    fn members(&mut self, node: Option<Node<'x, 'i>>) -> Result<Vec<Member>, DictionaryError> {
+        let mut members = Vec::new();
+        for node in node.into_iter().flat_map(|node| node.children()) {
+            let required = node.attribute("required") == Some("Y");
+            let member = match node.tag_name().name() {
+                "field" => Member::Field {
+                    tag: self.tag(attribute(node, "name")?)?,
+                    required,
+                },
+                "component" => {
+                    let name = attribute(node, "name")?;
+                    Member::Component {
+                        name: name.to_string(),
+                        required,
+                        members: self.component_members(name)?,
+                    }
+                }
+                "group" => {
+                    let name = attribute(node, "name")?;
+                    let members = self.members(Some(node))?;
+                    Member::Group(GroupDef {
+                        name: name.to_string(),
+                        no_tag: self.tag(name)?,
+                        required,
+                        delimiter: first_tag(&members).ok_or_else(|| {
+                            DictionaryError::EmptyGroup {
+                                name: name.to_string(),
+                            }
+                        })?,
+                        members,
+                    })
+                }
+                _ => continue, // eg: text, comments
+            };
+            members.push(member);
+        }
+        Ok(members)
+    }
+
+    fn component_members(&mut self, name: &str) -> Result<Vec<Member>, DictionaryError> {
+        if let Some(members) = self.components.get(name) {
+            return Ok(members.clone());
+        }
+        let (name, node) = self
+            .component_nodes
+            .get_key_value(name)
+            .map(|(name, node)| (*name, *node))
+            .ok_or_else(|| DictionaryError::UnknownComponent {
+                name: name.to_string(),
+            })?;
+        if self.loading.contains(&name) {
+            return Err(DictionaryError::RecursiveComponent {
+                name: name.to_string(),
+            });
+        }
+
+        self.loading.push(name);
+        let members = self.members(Some(node));
+        self.loading.pop();
+        let members = members?;
+        self.components.insert(name, members.clone());
+        Ok(members)
+    }
+
+    fn tag(&self, name: &str) -> Result<i32, DictionaryError> {
+        self.tags_by_name
+            .get(name)
+            .copied()
+            .ok_or_else(|| DictionaryError::UnknownField {
+                name: name.to_string(),
+            })
+    }
+}
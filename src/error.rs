@@ -0,0 +1,333 @@
+use crate::types::ValueType;
+use crate::ParseWarning;
+use std::fmt;
+
+/// Reasons why an input could not be parsed as a FIX message.
+///
+/// Every variant carries the byte offset (relative to the beginning of the input) where the
+/// problem was detected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    /// There is no `8=` in the input, so the beginning of the message can't be found.
+    MissingBeginString { offset: usize },
+    /// The value of tag 8 is not a FIX version (eg: `FIX.4.4`, `FIXT.1.1`, `FIX.5.0SP2`).
+    InvalidBeginString { offset: usize },
+    /// The input is shorter than the shortest parsable message.
+    TooShort { offset: usize, length: usize },
+    /// The field separator could not be detected after the BeginString.
+    MissingSeparator { offset: usize },
+    /// The stream could not be read (see [`FixStreamParser`](crate::FixStreamParser)). The offset
+    /// is relative to the beginning of the stream.
+    Io { message: String, offset: usize },
+    /// A repeating group was detected but the field before its first instance is not a NoXXX
+    /// field. Only returned if the options reject it (see
+    /// [`ParserOptions::reject_invalid_groups`](crate::ParserOptions::reject_invalid_groups)).
+    MissingGroupCount { tag: i32, offset: usize },
+    /// The NoXXX field of a repeating group does not have a numeric value. Only returned if the
+    /// options reject it (see
+    /// [`ParserOptions::reject_invalid_groups`](crate::ParserOptions::reject_invalid_groups)).
+    InvalidGroupCount {
+        tag: i32,
+        value: String,
+        offset: usize,
+    },
+    /// The message has an anomaly which the options reject (see
+    /// [`ParserOptions::strict`](crate::ParserOptions::strict)). It is the first one found in the
+    /// message.
+    Rejected { warning: ParseWarning },
+}
+
+impl ParseError {
+    /// Byte offset (relative to the beginning of the input) where the error was detected.
+    pub fn offset(&self) -> usize {
+        match self {
+            ParseError::MissingBeginString { offset }
+            | ParseError::InvalidBeginString { offset }
+            | ParseError::TooShort { offset, .. }
+            | ParseError::MissingSeparator { offset }
+            | ParseError::MissingGroupCount { offset, .. }
+            | ParseError::InvalidGroupCount { offset, .. }
+            | ParseError::Io { offset, .. } => *offset,
+            ParseError::Rejected { warning } => warning.offset,
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseError::MissingBeginString { .. } => write!(f, "BeginString (tag 8) not found"),
+            ParseError::InvalidBeginString { offset } => {
+                write!(f, "invalid BeginString (tag 8) at offset {}", offset)
+            }
+            ParseError::TooShort { offset, length } => write!(
+                f,
+                "message starting at offset {} is too short ({} bytes)",
+                offset, length
+            ),
+            ParseError::MissingSeparator { offset } => {
+                write!(
+                    f,
+                    "could not detect the field separator at offset {}",
+                    offset
+                )
+            }
+            ParseError::MissingGroupCount { tag, offset } => write!(
+                f,
+                "repeating group with delimiter {} at offset {} has no NoXXX field",
+                tag, offset
+            ),
+            ParseError::InvalidGroupCount { tag, value, offset } => write!(
+                f,
+                "invalid number of repetitions for group {} at offset {}: [{}]",
+                tag, offset, value
+            ),
+            ParseError::Io { message, offset } => {
+                write!(
+                    f,
+                    "could not read the stream at offset {}: {}",
+                    offset, message
+                )
+            }
+            ParseError::Rejected { warning } => {
+                write!(
+                    f,
+                    "message rejected at offset {}: {}",
+                    warning.offset, warning
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Reasons why the value of a field can't be read with a typed getter (eg: [`FixMessage::get_int`](crate::FixMessage::get_int)).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FieldError {
+    /// The value is not valid UTF-8.
+    InvalidUtf8 { tag: i32 },
+    /// The value does not have the format of the expected type.
+    InvalidValue {
+        tag: i32,
+        value: String,
+        expected: ValueType,
+    },
+    /// The value has the expected format but it doesn't fit in the returned type.
+    OutOfRange {
+        tag: i32,
+        value: String,
+        expected: ValueType,
+    },
+}
+
+impl FieldError {
+    /// Tag of the field whose value could not be read.
+    pub fn tag(&self) -> i32 {
+        match self {
+            FieldError::InvalidUtf8 { tag }
+            | FieldError::InvalidValue { tag, .. }
+            | FieldError::OutOfRange { tag, .. } => *tag,
+        }
+    }
+}
+
+impl fmt::Display for FieldError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FieldError::InvalidUtf8 { tag } => write!(f, "value of tag {} is not valid UTF-8", tag),
+            FieldError::InvalidValue {
+                tag,
+                value,
+                expected,
+            } => write!(
+                f,
+                "value of tag {} is not a valid {}: [{}]",
+                tag, expected, value
+            ),
+            FieldError::OutOfRange {
+                tag,
+                value,
+                expected,
+            } => write!(
+                f,
+                "value of tag {} is out of the range of {}: [{}]",
+                tag, expected, value
+            ),
+        }
+    }
+}
+
+impl std::error::Error for FieldError {}
+
+/// Reasons why a string can't be parsed as a [`FixPath`](crate::FixPath).
+///
+/// Every variant carries the byte offset (relative to the beginning of the path) where the
+/// problem was detected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PathError {
+    /// A tag is missing or it is not a number.
+    InvalidTag { offset: usize },
+    /// A selector is not `[N]`, `[*]` or `[?TAG=VALUE]` (or its `]` is missing).
+    InvalidSelector { offset: usize },
+    /// A tag or a selector is followed by something other than `.`.
+    UnexpectedCharacter { offset: usize },
+    /// The last tag of the path has a selector, but it must be the tag of a field.
+    SelectorOnField { offset: usize },
+}
+
+impl PathError {
+    /// Byte offset (relative to the beginning of the path) where the error was detected.
+    pub fn offset(&self) -> usize {
+        match self {
+            PathError::InvalidTag { offset }
+            | PathError::InvalidSelector { offset }
+            | PathError::UnexpectedCharacter { offset }
+            | PathError::SelectorOnField { offset } => *offset,
+        }
+    }
+}
+
+impl fmt::Display for PathError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PathError::InvalidTag { offset } => write!(f, "invalid tag at offset {}", offset),
+            PathError::InvalidSelector { offset } => {
+                write!(f, "invalid selector at offset {}", offset)
+            }
+            PathError::UnexpectedCharacter { offset } => {
+                write!(f, "unexpected character at offset {}", offset)
+            }
+            PathError::SelectorOnField { offset } => write!(
+                f,
+                "the last tag of the path can't have a selector (offset {})",
+                offset
+            ),
+        }
+    }
+}
+
+impl std::error::Error for PathError {}
+
+/// Reasons why a json value can't be converted to a FIX message (see
+/// [`FixMessage::from_json`](crate::FixMessage::from_json)).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JsonError {
+    /// The message is not a json object.
+    NotAnObject,
+    /// A key is not a tag (ie: a number).
+    InvalidTag { key: String },
+    /// The value of a tag is not a string, a number or an array (of group instances).
+    InvalidValue { tag: i32 },
+    /// An instance of a group is not a json object.
+    InvalidGroupInstance { tag: i32, index: usize },
+}
+
+impl fmt::Display for JsonError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            JsonError::NotAnObject => write!(f, "the message is not a json object"),
+            JsonError::InvalidTag { key } => write!(f, "invalid tag [{}]", key),
+            JsonError::InvalidValue { tag } => write!(f, "invalid value for tag {}", tag),
+            JsonError::InvalidGroupInstance { tag, index } => write!(
+                f,
+                "instance {} of group {} is not a json object",
+                index, tag
+            ),
+        }
+    }
+}
+
+impl std::error::Error for JsonError {}
+
+/// Reason why a message can't be deserialized into a type (see
+/// [`FixMessage::deserialize_into`](crate::FixMessage::deserialize_into)).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeserializeError {
+    message: String,
+}
+
+impl fmt::Display for DeserializeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for DeserializeError {}
+
+impl serde::de::Error for DeserializeError {
+    fn custom<T: fmt::Display>(message: T) -> Self {
+        Self {
+            message: message.to_string(),
+        }
+    }
+}
+
+/// Reasons why a dictionary can't be loaded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DictionaryError {
+    /// The file can't be read.
+    Io { message: String },
+    /// The content is not well-formed XML or it doesn't have the expected root element.
+    Xml { message: String },
+    /// An element doesn't have a mandatory attribute (eg: a field without `number`).
+    MissingAttribute { element: String, attribute: String },
+    /// The number of a field is not a valid tag.
+    InvalidTag { value: String },
+    /// A field is referenced (by name or id) but it is not defined.
+    UnknownField { name: String },
+    /// A component is referenced (by name or id) but it is not defined.
+    UnknownComponent { name: String },
+    /// A component or a group contains itself (directly or through other components or groups).
+    RecursiveComponent { name: String },
+    /// A group is referenced by id but it is not defined.
+    UnknownGroup { name: String },
+    /// A group has no members, so its delimiter is unknown.
+    EmptyGroup { name: String },
+}
+
+impl fmt::Display for DictionaryError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DictionaryError::Io { message } => {
+                write!(f, "could not read the dictionary: {}", message)
+            }
+            DictionaryError::Xml { message } => write!(f, "invalid dictionary: {}", message),
+            DictionaryError::MissingAttribute { element, attribute } => {
+                write!(f, "element <{}> has no attribute [{}]", element, attribute)
+            }
+            DictionaryError::InvalidTag { value } => write!(f, "invalid tag [{}]", value),
+            DictionaryError::UnknownField { name } => write!(f, "unknown field [{}]", name),
+            DictionaryError::UnknownComponent { name } => {
+                write!(f, "unknown component [{}]", name)
+            }
+            DictionaryError::RecursiveComponent { name } => {
+                write!(f, "component [{}] contains itself", name)
+            }
+            DictionaryError::UnknownGroup { name } => write!(f, "unknown group [{}]", name),
+            DictionaryError::EmptyGroup { name } => write!(f, "group [{}] has no members", name),
+        }
+    }
+}
+
+impl std::error::Error for DictionaryError {}
+
+/// Reasons why a log format can't be created (see [`LogFormat::new`](crate::LogFormat::new)).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LogFormatError {
+    /// The regex is not valid.
+    InvalidRegex { message: String },
+    /// The regex has a named capture other than `timestamp`, `direction` and `session_id`.
+    UnknownCapture { name: String },
+}
+
+impl fmt::Display for LogFormatError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LogFormatError::InvalidRegex { message } => write!(f, "invalid regex: {}", message),
+            LogFormatError::UnknownCapture { name } => write!(f, "unknown capture [{}]", name),
+        }
+    }
+}
+
+impl std::error::Error for LogFormatError {}
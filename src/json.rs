@@ -0,0 +1,102 @@
+use crate::model::{FixComponent, FixEntity, FixGroup};
+use crate::{ComponentView, Dictionary, FieldDef, JsonError};
+use serde_json::{Map, Value};
+
+// rebuilds a component from its json representation (see FixMessage::to_json). Arrays are groups
+// (the key being the NoXXX tag) whose elements are their instances
+pub(crate) fn component_from_json(
+    object: &Map<String, Value>,
+) -> Result<FixComponent<Vec<u8>>, JsonError> {
+    let mut entities = Vec::with_capacity(object.len());
+    for (key, value) in object {
+        let tag = key.parse::<i32>().map_err(|_| JsonError::InvalidTag {
+            key: key.to_string(),
+        })?;
+        let entity = match value {
+            Value::String(value) => FixEntity::Field(tag, value.clone().into_bytes()),
+            Value::Number(value) => FixEntity::Field(tag, value.to_string().into_bytes()),
+            Value::Array(instances) => {
+                let instances = instances
+                    .iter()
+                    .enumerate()
+                    .map(|(index, instance)| match instance {
+                        Value::Object(instance) => component_from_json(instance),
+                        _ => Err(JsonError::InvalidGroupInstance { tag, index }),
+                    })
+                    .collect::<Result<_, _>>()?;
+                FixEntity::Group(FixGroup::from_instances(tag, instances))
+            }
+            _ => return Err(JsonError::InvalidValue { tag }),
+        };
+        entities.push(entity);
+    }
+    Ok(FixComponent::new(entities))
+}
+
+// json representation of a message whose standard header, body and standard trailer are nested
+// objects, as in the FIX JSON encoding
+pub(crate) fn component_to_sectioned_json<V: AsRef<[u8]>>(component: &FixComponent<V>) -> Value {
+    let [header, body, trailer] = component.sections();
+    serde_json::json!({
+        "Header": ComponentView::from_entities(header),
+        "Body": ComponentView::from_entities(body),
+        "Trailer": ComponentView::from_entities(trailer),
+    })
+}
+
+// json representation of a component whose keys are the names of the fields in the dictionary
+// (or their tags if they are not defined). If annotated, the values of the fields are objects
+// (see FixMessage::to_annotated_json)
+pub(crate) fn component_to_named_json<V: AsRef<[u8]>>(
+    component: &FixComponent<V>,
+    dictionary: &Dictionary,
+    annotated: bool,
+) -> Value {
+    let mut object = Map::with_capacity(component.entities.len());
+    for entity in &component.entities {
+        match entity {
+            FixEntity::Field(tag, value) => {
+                let field = dictionary.field(*tag);
+                let value = String::from_utf8_lossy(value.as_ref()).into_owned();
+                let value = if annotated {
+                    annotated_value(*tag, field, value)
+                } else {
+                    Value::String(value)
+                };
+                object.insert(key(*tag, field), value);
+            }
+            FixEntity::Group(group) => {
+                let instances = group
+                    .instances
+                    .iter()
+                    .map(|instance| component_to_named_json(instance, dictionary, annotated))
+                    .collect();
+                object.insert(
+                    key(group.no_tag, dictionary.field(group.no_tag)),
+                    Value::Array(instances),
+                );
+            }
+        }
+    }
+    Value::Object(object)
+}
+
+fn key(tag: i32, field: Option<&FieldDef>) -> String {
+    field.map_or_else(|| tag.to_string(), |field| field.name.clone())
+}
+
+// eg: {"tag":54,"name":"Side","value":"1","enum":"BUY"} (name and enum are only set if known)
+fn annotated_value(tag: i32, field: Option<&FieldDef>, value: String) -> Value {
+    let mut object = Map::new();
+    object.insert(String::from("tag"), Value::from(tag));
+    if let Some(field) = field {
+        object.insert(String::from("name"), Value::from(field.name.as_str()));
+    }
+    let description = field.and_then(|field| field.value_description(&value));
+    let description = description.map(String::from);
+    object.insert(String::from("value"), Value::String(value));
+    if let Some(description) = description {
+        object.insert(String::from("enum"), Value::String(description));
+    }
+    Value::Object(object)
+}
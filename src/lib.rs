@@ -1,7 +1,7 @@
 //! `fixparser` is a Rust library to decode FIX (Financial Information eXchange) messages.
 //!
 //! - It supports groups and you don't need a FIX dictionary
-//! - You don't need to specify the separator of the input string as long as they are consistent. eg: 0x01, |, etc...
+//! - You don't need to specify the separator of the input string as long as they are consistent. eg: 0x01, |, ^A, \x01 (literal), etc... It can also be given (or a list of allowed ones) with `ParserOptions::separator` (or `ParserOptions::allowed_separators`)
 //! - You don't need to "trim" the input string as the lib detects the beginning and end of the message
 //!
 //! Currently supported input:
@@ -12,18 +12,6 @@
 //!
 //! - Json (serde_json::value::Value)
 
-use serde::{ser::SerializeMap, Serialize, Serializer};
-use std::collections::{HashMap, HashSet, VecDeque};
-use wasm_bindgen::prelude::*;
-
-#[wasm_bindgen]
-pub fn from_tag_value_to_json(input_message: &str) -> String {
-    match FixMessage::from_tag_value(input_message) {
-      Some(fix_message) => fix_message.to_json().to_string(),
-      None => String::from(r#"{"error": "Could not parse the given input. Is it a valid FIX message?"}"#)
-    }
-}
-
 #[cfg(feature = "debugging")]
 macro_rules! debug {
     ($($arg:tt)*) => { println!($($arg)*); }
@@ -34,115 +22,118 @@ macro_rules! debug {
     ($($arg:tt)*) => {};
 }
 
-#[derive(Debug, Clone)]
-enum FixEntity {
-    Field(i32, String),
-    Group(FixGroup),
-}
-
-impl FixEntity {
-    fn get_tag(&self) -> i32 {
-        match self {
-            FixEntity::Field(tag, _dummy) => *tag,
-            FixEntity::Group(group) => group.no_tag,
-        }
-    }
+mod builder;
+mod data_fields;
+mod de;
+mod diagnostics;
+mod dictionary;
+mod error;
+mod json;
+mod log_line;
+mod message_ref;
+mod model;
+mod options;
+mod parser;
+mod query;
+mod stream;
+mod tag_value;
+mod types;
+mod validation;
+mod verification;
+mod view;
+mod view_mut;
+
+pub use builder::{ComponentBuilder, FixMessageBuilder, GroupBuilder};
+pub use data_fields::STANDARD_DATA_FIELDS;
+pub use diagnostics::{ParseWarning, WarningKind};
+pub use dictionary::{
+    ComponentDef, Dictionary, FieldDef, FieldValue, GroupDef, Member, MessageDef,
+};
+pub use error::{
+    DeserializeError, DictionaryError, FieldError, JsonError, LogFormatError, ParseError, PathError,
+};
+pub use log_line::{Direction, LogFormat, LogPrefix};
+pub use message_ref::FixMessageRef;
+pub use options::ParserOptions;
+pub use query::FixPath;
+pub use stream::FixStreamParser;
+pub use types::{Decimal, LocalMktDate, MonthYear, UtcTimestamp, ValueError, ValueType};
+pub use validation::{IssueKind, ValidationIssue};
+pub use verification::{BodyLengthCheck, ChecksumCheck};
+pub use view::{ComponentView, EntityView, GroupView};
+pub use view_mut::ComponentMut;
+
+use model::FixComponent;
+use parser::Parser;
+use serde::{Deserialize, Serialize, Serializer};
+use std::borrow::Cow;
+use std::str::Utf8Error;
+use wasm_bindgen::prelude::*;
 
-    fn get_field_value_i32(&self) -> i32 {
-        if let FixEntity::Field(_dummy, value) = self {
-            return value.parse().unwrap();
-        }
-        panic!("A Field was expected");
+#[wasm_bindgen]
+pub fn from_tag_value_to_json(input_message: &str) -> String {
+    match FixMessage::from_tag_value(input_message) {
+        Ok(fix_message) => fix_message.to_json().to_string(),
+        Err(error) => serde_json::json!({ "error": error.to_string() }).to_string(),
     }
 }
 
-#[derive(Debug, Clone)]
-struct FixComponent {
-    entities: Vec<FixEntity>,
-}
-
-impl FixComponent {
-    fn new(entities: Vec<FixEntity>) -> Self {
-        Self { entities }
+/// Same as [`from_tag_value_to_json`], but the message is parsed with the given dictionary (see
+/// [`FixMessage::from_tag_value_with_dictionary`]) and the keys are the names of the fields (see
+/// [`FixMessage::to_json_with_names`]). If `annotated` is true, the values are annotated (see
+/// [`FixMessage::to_annotated_json`]).
+///
+/// The dictionary can be in QuickFIX or FIX Orchestra format.
+#[cfg(feature = "dictionary")]
+#[wasm_bindgen]
+pub fn from_tag_value_to_named_json(
+    input_message: &str,
+    dictionary_xml: &str,
+    annotated: bool,
+) -> String {
+    let dictionary = match Dictionary::from_quickfix_xml(dictionary_xml) {
+        Err(DictionaryError::Xml { .. }) => Dictionary::from_orchestra_xml(dictionary_xml),
+        dictionary => dictionary,
+    };
+    let dictionary = match dictionary {
+        Ok(dictionary) => dictionary,
+        Err(error) => return serde_json::json!({ "error": error.to_string() }).to_string(),
+    };
+    match FixMessage::from_tag_value_with_dictionary(input_message, &dictionary) {
+        Ok(fix_message) if annotated => fix_message.to_annotated_json(&dictionary).to_string(),
+        Ok(fix_message) => fix_message.to_json_with_names(&dictionary).to_string(),
+        Err(error) => serde_json::json!({ "error": error.to_string() }).to_string(),
     }
 }
 
-impl Serialize for FixComponent {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: Serializer,
-    {
-        let mut map = serializer.serialize_map(Some(self.entities.len()))?;
-        for entity in &self.entities {
-            match entity {
-                FixEntity::Field(ref tag, ref value) => {
-                    map.serialize_entry(tag, value)?;
-                }
-                FixEntity::Group(ref group) => {
-                    map.serialize_entry(&group.no_tag, &group.instances)?;
-                }
-            }
-        }
-        map.end()
+/// Rebuilds a message from its json representation (see [`FixMessage::from_json`]) and encodes
+/// it in tag=value format, with SOH as the separator.
+#[wasm_bindgen]
+pub fn from_json_to_tag_value(input_json: &str) -> String {
+    let fix_message = serde_json::from_str(input_json)
+        .map_err(|error| error.to_string())
+        .and_then(|json| FixMessage::from_json(&json).map_err(|error| error.to_string()));
+    match fix_message {
+        Ok(fix_message) => String::from_utf8_lossy(&fix_message.to_tag_value()).into_owned(),
+        Err(error) => serde_json::json!({ "error": error }).to_string(),
     }
 }
 
-#[derive(Debug, Clone)]
-struct FixGroup {
-    delimiter: i32, // first tag of each group instance
-    no_tag: i32,    // tag which contains the number of repetitions
-    repetitions: i32,
-    current_iteration: i32,
-    known_tags: HashSet<i32>, // tags we know that belong to this group
-    instances: Vec<FixComponent>,
-}
-
-impl FixGroup {
-    fn new(delimiter: i32, index_first_delimiter: usize, component: &mut FixComponent) -> Self {
-        let group_instance =
-            FixComponent::new(component.entities.drain(index_first_delimiter..).collect());
-        let no_tag_field = component.entities.pop().unwrap();
-
-        Self {
-            no_tag: no_tag_field.get_tag(), // bad variable name, as in FIX
-            delimiter,
-            repetitions: no_tag_field.get_field_value_i32(),
-            current_iteration: 1,
-            known_tags: Self::get_known_tags(&group_instance),
-            instances: vec![group_instance],
-        }
-    }
-
-    fn get_known_tags(group_instance: &FixComponent) -> HashSet<i32> {
-        let mut known_tags = HashSet::<i32>::new();
-        group_instance
-            .entities
-            .iter()
-            .for_each(|entity| match entity {
-                FixEntity::Field(tag, _value) => {
-                    known_tags.insert(*tag);
-                }
-                FixEntity::Group(group) => {
-                    group.known_tags.iter().for_each(|known_tag| {
-                        known_tags.insert(*known_tag);
-                    });
-                }
-            });
-        known_tags
-    }
-
-    fn create_new_instance(&mut self) {
-        self.instances.push(FixComponent::new(Vec::new()));
-    }
-
-    fn insert_known_tag(&mut self, tag: i32) {
-        self.known_tags.insert(tag);
+/// Get the values selected by the given path (see [`FixPath`]) as a json array of strings.
+///
+/// eg: `query_tag_value("8=FIX.4.4 | 453=2 | 448=A | 448=B | 10=209", "453[*].448")` returns `["A","B"]`.
+#[wasm_bindgen]
+pub fn query_tag_value(input_message: &str, path: &str) -> String {
+    let path = match path.parse::<FixPath>() {
+        Ok(path) => path,
+        Err(error) => return serde_json::json!({ "error": error.to_string() }).to_string(),
+    };
+    match FixMessage::from_tag_value(input_message) {
+        Ok(fix_message) => serde_json::json!(fix_message.query(&path)).to_string(),
+        Err(error) => serde_json::json!({ "error": error.to_string() }).to_string(),
     }
 }
 
-#[derive(Debug)]
-struct TagValue<'a>(i32, &'a str);
-
 /// This is the interface you interact with.
 ///
 /// The internal message is represented as follows:
@@ -153,28 +144,27 @@ struct TagValue<'a>(i32, &'a str);
 ///
 /// FixEntity     := Field | Group
 ///
-/// Field         := (tag: i32, value: String)
+/// Field         := (tag: i32, value: bytes)
 /// Group         := FixComponent*
 /// ```
 pub struct FixMessage {
-    root_component: FixComponent,
-    pending_tag_indices: HashMap<i32, VecDeque<usize>>,
-    candidate_indices: Vec<HashMap<i32, usize>>, // store indices of tags of potential nested group
-    active_groups: Vec<FixGroup>,                // contains the groups currently being parsed
+    root_component: FixComponent<Vec<u8>>,
+    warnings: Vec<ParseWarning>,
+    checksum: Option<ChecksumCheck>,
+    body_length: Option<BodyLengthCheck>,
 }
 
-impl FixMessage {
-    fn new() -> Self {
-        let mut candidate_indices = Vec::new();
-        candidate_indices.push(HashMap::new());
-        Self {
-            root_component: FixComponent::new(Vec::new()),
-            pending_tag_indices: HashMap::new(),
-            candidate_indices,
-            active_groups: Vec::new(),
-        }
+/// The message is serialized as [`FixMessage::to_json`] shows it, with any serde format.
+impl Serialize for FixMessage {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.root_component.serialize(serializer)
     }
+}
 
+impl FixMessage {
     /// Creates a FixMessage from an input string encoded in [FIX Tag=Value (classic FIX)](https://www.fixtrading.org/standards/tagvalue/).
     ///
     /// # Example
@@ -183,25 +173,193 @@ impl FixMessage {
     /// let input = "Recv | 8=FIX.4.4 | 555=2 | 600=CGY | 604=2 | 605=F7 | 605=CGYU0 | 600=CGY | 10=209";
     /// println!("{}", fixparser::FixMessage::from_tag_value(&input).unwrap().to_json());
     /// ```
-    pub fn from_tag_value(input_message: &str) -> Option<FixMessage> {
-        let tag_values = FixMessage::pre_process_message(&input_message)?;
-        let mut message = FixMessage::new();
+    ///
+    /// # Errors
+    ///
+    /// A [`ParseError`] is returned if the input can't be parsed as a FIX message (eg: there is no
+    /// BeginString, the separator can't be detected or a repeating group has an invalid NoXXX value).
+    pub fn from_tag_value(input_message: &str) -> Result<FixMessage, ParseError> {
+        Self::from_tag_value_with(input_message, &ParserOptions::default())
+    }
+
+    /// Creates a FixMessage from raw bytes encoded in [FIX Tag=Value (classic FIX)](https://www.fixtrading.org/standards/tagvalue/).
+    ///
+    /// Unlike [`FixMessage::from_tag_value`], the input doesn't need to be valid UTF-8 (eg: fields
+    /// encoded in Latin-1 or Shift-JIS, or binary data fields). The values are kept as bytes.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// let input = b"8=FIX.4.4 | 347=ISO-8859-1 | 58=Se\xf1or | 10=209";
+    /// let fix_message = fixparser::FixMessage::from_bytes(input).unwrap();
+    /// assert_eq!(Some(&b"Se\xf1or"[..]), fix_message.get_bytes(58));
+    /// assert_eq!("Se\u{FFFD}or", fix_message.get_lossy(58).unwrap());
+    /// assert!(fix_message.get_str(58).unwrap().is_err());
+    /// ```
+    pub fn from_bytes(input_message: &[u8]) -> Result<FixMessage, ParseError> {
+        Self::from_bytes_with(input_message, &ParserOptions::default())
+    }
 
-        for (index, tag_value) in tag_values.iter().enumerate() {
-            message
-                .pending_tag_indices
-                .entry(tag_value.0)
-                .or_insert_with(VecDeque::new)
-                .push_back(index);
-        }
-        message.check_message_is_valid();
+    /// Same as [`FixMessage::from_tag_value`], but the parsing is customized with the given options.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// let options = fixparser::ParserOptions::new().verify_checksum(true);
+    /// let input = "8=FIX.4.4 | 9=5 | 35=0 | 10=000";
+    /// let fix_message = fixparser::FixMessage::from_tag_value_with(&input, &options).unwrap();
+    /// println!("{:?}", fix_message.checksum());
+    /// ```
+    pub fn from_tag_value_with(
+        input_message: &str,
+        options: &ParserOptions,
+    ) -> Result<FixMessage, ParseError> {
+        Self::from_bytes_with(input_message.as_bytes(), options)
+    }
+
+    /// Same as [`FixMessage::from_bytes`], but the parsing is customized with the given options.
+    pub fn from_bytes_with(
+        input_message: &[u8],
+        options: &ParserOptions,
+    ) -> Result<FixMessage, ParseError> {
+        let parsed_message = Parser::parse(input_message, options)?;
+        Ok(Self {
+            root_component: parsed_message.root_component,
+            warnings: parsed_message.warnings,
+            checksum: parsed_message.checksum,
+            body_length: parsed_message.body_length,
+        })
+    }
 
-        for (index, tag_value) in tag_values.iter().enumerate() {
-            message.add_tag_value(tag_value.0, String::from(tag_value.1), index);
-        }
-        message.clean();
+    /// Same as [`FixMessage::from_tag_value`], but the repeating groups are parsed as the
+    /// dictionary defines them for the type of the message (tag 35): a group starts with its
+    /// delimiter right after its NoXXX field, and it ends with the first tag which is not one of
+    /// its members.
+    ///
+    /// The tags the dictionary doesn't define (eg: custom fields in a group) are parsed as with
+    /// [`FixMessage::from_tag_value`], as well as the whole message if its type is not defined.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "dictionary")]
+    /// # fn main() {
+    /// # let xml = r#"
+    /// # <fix major="4" minor="4">
+    /// #   <messages>
+    /// #     <message name="CustomMessage" msgtype="U1" msgcat="app">
+    /// #       <group name="CustomGroup" required="N">
+    /// #         <field name="CustomDelimiter" required="Y"/>
+    /// #         <field name="CustomValue" required="N"/>
+    /// #         <field name="CustomField" required="N"/>
+    /// #       </group>
+    /// #     </message>
+    /// #   </messages>
+    /// #   <fields>
+    /// #     <field number="1000" name="CustomGroup" type="NUMINGROUP"/>
+    /// #     <field number="1001" name="CustomDelimiter" type="INT"/>
+    /// #     <field number="1002" name="CustomValue" type="INT"/>
+    /// #     <field number="1003" name="CustomField" type="INT"/>
+    /// #   </fields>
+    /// # </fix>"#;
+    /// // 1003 is a member of group 1000 in the dictionary
+    /// let dictionary = fixparser::Dictionary::from_quickfix_xml(xml).unwrap();
+    /// let input = "8=FIX.4.4 | 35=U1 | 1000=2 | 1001=1 | 1002=2 | 1001=10 | 1002=20 | 1003=30 | 10=209";
+    /// let fix_message = fixparser::FixMessage::from_tag_value_with_dictionary(&input, &dictionary).unwrap();
+    /// let path = "1000[1].1003".parse().unwrap();
+    /// assert_eq!(vec!["30"], fix_message.query(&path));
+    /// # }
+    /// # #[cfg(not(feature = "dictionary"))]
+    /// # fn main() {}
+    /// ```
+    pub fn from_tag_value_with_dictionary(
+        input_message: &str,
+        dictionary: &Dictionary,
+    ) -> Result<FixMessage, ParseError> {
+        Self::from_tag_value_with_dictionary_with(
+            input_message,
+            dictionary,
+            &ParserOptions::default(),
+        )
+    }
+
+    /// Same as [`FixMessage::from_tag_value_with_dictionary`], but the parsing is customized with
+    /// the given options (see [`FixMessage::from_tag_value_with`]).
+    pub fn from_tag_value_with_dictionary_with(
+        input_message: &str,
+        dictionary: &Dictionary,
+        options: &ParserOptions,
+    ) -> Result<FixMessage, ParseError> {
+        let parsed_message =
+            Parser::parse_with_dictionary(input_message.as_bytes(), options, Some(dictionary))?;
+        Ok(Self {
+            root_component: parsed_message.root_component,
+            warnings: parsed_message.warnings,
+            checksum: parsed_message.checksum,
+            body_length: parsed_message.body_length,
+        })
+    }
 
-        Some(message)
+    /// Same as [`FixMessage::from_tag_value`], but the text found before the BeginString (eg: a
+    /// timestamp and the direction of the message) is returned as well. Its metadata is recognized
+    /// with the first of the given formats which matches it (see [`LogFormat`]).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fixparser::{FixMessage, LogFormat};
+    ///
+    /// let line = "20200520-19:15:45.134 OUT 8=FIX.4.4|35=0|10=209";
+    /// let (prefix, fix_message) = FixMessage::from_log_line(line, &LogFormat::builtin()).unwrap();
+    /// assert_eq!(Some("OnixS"), prefix.format.as_deref());
+    /// assert_eq!(Some("20200520-19:15:45.134"), prefix.timestamp.as_deref());
+    /// ```
+    pub fn from_log_line(
+        line: &str,
+        formats: &[LogFormat],
+    ) -> Result<(LogPrefix, FixMessage), ParseError> {
+        Self::from_log_line_with(line, formats, &ParserOptions::default())
+    }
+
+    /// Same as [`FixMessage::from_log_line`], but the message is parsed with the given options
+    /// (see [`FixMessage::from_tag_value_with`]).
+    pub fn from_log_line_with(
+        line: &str,
+        formats: &[LogFormat],
+        options: &ParserOptions,
+    ) -> Result<(LogPrefix, FixMessage), ParseError> {
+        let fix_message = Self::from_tag_value_with(line, options)?;
+        let start = parser::begin_string_start(line.as_bytes()).unwrap_or_default();
+        Ok((
+            log_line::recognize_prefix(&line[..start], formats),
+            fix_message,
+        ))
+    }
+
+    /// Creates a FixMessage from its json representation (as returned by [`FixMessage::to_json`]).
+    ///
+    /// Fields are kept in the order of the keys, and arrays are converted to repeating groups
+    /// whose NoXXX field is the key of the array. The values of BodyLength and CheckSum are kept
+    /// as they are, but they are recomputed by [`FixMessage::to_tag_value`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// let json = serde_json::json!({"8": "FIX.4.4", "35": "0", "453": [{"448": "A"}, {"448": "B"}]});
+    /// let fix_message = fixparser::FixMessage::from_json(&json).unwrap();
+    /// assert_eq!(
+    ///     &b"8=FIX.4.4|9=23|35=0|453=2|448=A|448=B|10=030|"[..],
+    ///     &fix_message.to_tag_value_with(b"|")[..]
+    /// );
+    /// ```
+    pub fn from_json(json: &serde_json::Value) -> Result<FixMessage, JsonError> {
+        let object = json.as_object().ok_or(JsonError::NotAnObject)?;
+        Ok(Self {
+            root_component: json::component_from_json(object)?,
+            warnings: Vec::new(),
+            checksum: None,
+            body_length: None,
+        })
     }
 
     /// Get a representation of the message in json string format.
@@ -221,248 +379,453 @@ impl FixMessage {
         serde_json::json!(&self.root_component)
     }
 
-    // from tag value encoding to a list of TagValue's
-    fn pre_process_message<'a>(input_message: &'a str) -> Option<Vec<TagValue<'a>>> {
-        const SHORTEST_MESSAGE_LENGTH: usize = 12; // len(8=FIX.N.M|X=) -> invalid still parsable
-        // trim input
-        let input_message = &input_message[input_message.find("8=")?..];
-        if input_message.len() < SHORTEST_MESSAGE_LENGTH {
-            return None;
-        }
-
-        let mut end_of_message_found = false;
-        input_message
-            .split(&Self::get_separator(input_message)?)
-            .map(|tag_value| {
-                tag_value.split_at(tag_value.find('=').unwrap_or_else(|| tag_value.len()))
-            })
-            .inspect(|tag_value| {
-                if tag_value.1.is_empty() {
-                    eprintln!("WARNING: Ignoring [{}]", tag_value.0);
-                } else if tag_value.1.len() == 1 {
-                    eprintln!("WARNING: Tag {} has no value", tag_value.0);
-                }
-            })
-            .filter(|tag_value| !tag_value.1.is_empty())
-            .map(|tag_value| {
-                let tag = tag_value.0.parse().unwrap_or(0);
-                let value = &tag_value.1[1..];
-                if tag == 10 && value.len() > 3 {
-                    debug!("Ignoring characters after checksum [{}]", &value[3..]);
-                    return TagValue(tag, &value[0..3]);
-                }
-                if tag == 0 {
-                    eprintln!("WARNING: Ignoring [{}={}]", tag_value.0, value);
-                }
-                TagValue(tag, value)
-            })
-            .take_while(|tag_value| {
-                if end_of_message_found {
-                    eprintln!("WARNING: Detected tag after tag 10: {}", tag_value.0);
-                    return false;
-                }
-                end_of_message_found = tag_value.0 == 10;
-                true
-            })
-            .filter(|tag_value| tag_value.0 != 0)
-            .map(Some)
-            .collect()
-    }
-
-    // get FIX values separator: eg: 0x01 or |
-    fn get_separator(fix_msg: &str) -> Option<String> {
-        let fix_version_re = regex::Regex::new(r"^8=FIXT?.\d{1}.\d{1}").unwrap();
-        let field_separator = &fix_msg[fix_version_re.shortest_match(fix_msg)?..]
-            .chars()
-            .take_while(|char| !char.is_digit(10))
-            .collect::<String>();
-
-        if field_separator == "" {
-            return None;
-        }
-        Some(field_separator.to_string())
-    }
-
-    fn check_message_is_valid(&self) {
-        if self.pending_tag_indices.get(&10).is_none() {
-            eprintln!("WARNING: Message is incomplete (missing tag 10)");
-        }
-    }
-
-    #[allow(unused_variables)]
-    fn add_tag_value(&mut self, tag: i32, value: String, index: usize) {
-        debug!(
-            "{}Index {} - Add {} - {}",
-            self.get_spaces(),
-            index,
-            tag,
-            value
-        );
-        self.remove_pending_tag(tag);
+    /// Same as [`FixMessage::to_json`], but the fields of the standard header, the body and the
+    /// standard trailer are nested in their own objects (as in the FIX JSON encoding). See
+    /// [`FixMessage::header`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// let input = "8=FIX.4.4 | 9=5 | 35=0 | 49=A | 56=B | 58=hi | 10=209";
+    /// let fix_message = fixparser::FixMessage::from_tag_value(&input).unwrap();
+    /// assert_eq!(
+    ///     r#"{"Header":{"8":"FIX.4.4","9":"5","35":"0","49":"A","56":"B"},"Body":{"58":"hi"},"Trailer":{"10":"209"}}"#,
+    ///     fix_message.to_json_with_sections().to_string()
+    /// );
+    /// ```
+    pub fn to_json_with_sections(&self) -> serde_json::value::Value {
+        json::component_to_sectioned_json(&self.root_component)
+    }
 
-        while self.is_parsing_group() && !self.tag_in_group(tag) {
-            self.close_group();
-        }
+    /// Same as [`FixMessage::to_json`], but the keys are the names of the fields in the given
+    /// dictionary. The tags which are not defined in the dictionary are kept as keys.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "dictionary")]
+    /// # fn main() {
+    /// # let xml = r#"
+    /// # <fix major="4" minor="4">
+    /// #   <fields>
+    /// #     <field number="8" name="BeginString" type="STRING"/>
+    /// #     <field number="555" name="NoLegs" type="NUMINGROUP"/>
+    /// #     <field number="600" name="LegSymbol" type="STRING"/>
+    /// #   </fields>
+    /// # </fix>"#;
+    /// let dictionary = fixparser::Dictionary::from_quickfix_xml(xml).unwrap();
+    /// let input = "8=FIX.4.4 | 555=2 | 600=CGY | 600=CGZ | 10=209";
+    /// let fix_message = fixparser::FixMessage::from_tag_value(&input).unwrap();
+    /// assert_eq!(
+    ///     r#"{"BeginString":"FIX.4.4","NoLegs":[{"LegSymbol":"CGY"},{"LegSymbol":"CGZ"}],"10":"209"}"#,
+    ///     fix_message.to_json_with_names(&dictionary).to_string()
+    /// );
+    /// # }
+    /// # #[cfg(not(feature = "dictionary"))]
+    /// # fn main() {}
+    /// ```
+    pub fn to_json_with_names(&self, dictionary: &Dictionary) -> serde_json::value::Value {
+        json::component_to_named_json(&self.root_component, dictionary, false)
+    }
 
-        if self.repeated_candidate(tag) {
-            self.open_group(tag);
-        }
+    /// Same as [`FixMessage::to_json_with_names`], but each value is an object with the tag, the
+    /// name of the field, the value and the description of the value (if they are defined in the
+    /// dictionary).
+    ///
+    /// ```ignore
+    /// {"Side":{"tag":54,"name":"Side","value":"1","enum":"BUY"},"9999":{"tag":9999,"value":"X"}}
+    /// ```
+    ///
+    /// Repeating groups are arrays of instances as in [`FixMessage::to_json`].
+    pub fn to_annotated_json(&self, dictionary: &Dictionary) -> serde_json::value::Value {
+        json::component_to_named_json(&self.root_component, dictionary, true)
+    }
 
-        if self.is_parsing_group() {
-            self.set_known_tag_in_group(tag);
-        }
+    /// Encodes the message in [FIX Tag=Value (classic FIX)](https://www.fixtrading.org/standards/tagvalue/)
+    /// with SOH as the separator.
+    ///
+    /// BodyLength (tag 9) and CheckSum (tag 10) are recomputed, and the NoXXX field of each group
+    /// is set to the number of its instances. If there is no BeginString (tag 8), the fields are
+    /// encoded as they are, without adding BodyLength nor CheckSum.
+    pub fn to_tag_value(&self) -> Vec<u8> {
+        self.to_tag_value_with(tag_value::SOH)
+    }
 
-        if self.is_new_iteration(tag) {
-            self.create_new_group_instance();
-        } else {
-            self.register_candidate(tag);
-        }
+    /// Same as [`FixMessage::to_tag_value`], but the fields are separated with the given separator.
+    ///
+    /// BodyLength and CheckSum are computed as if SOH was the separator.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// let input = "8=FIX.4.4 | 9=999 | 35=0 | 453=5 | 448=A | 448=B | 10=000";
+    /// let fix_message = fixparser::FixMessage::from_tag_value(&input).unwrap();
+    /// assert_eq!(
+    ///     &b"8=FIX.4.4|9=23|35=0|453=2|448=A|448=B|10=030|"[..],
+    ///     &fix_message.to_tag_value_with(b"|")[..]
+    /// );
+    /// ```
+    pub fn to_tag_value_with(&self, separator: &[u8]) -> Vec<u8> {
+        tag_value::encode(&self.root_component, separator)
+    }
 
-        self.get_entities().push(FixEntity::Field(tag, value));
+    /// Get the value of the first field with the given tag.
+    ///
+    /// It is `None` if there is no such field or if its value is not valid UTF-8 (see
+    /// [`FixMessage::get_str`]). Only the fields of the message are looked up (ie: fields inside
+    /// repeating groups are not).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// let input = "8=FIX.4.4 | 35=AB | 555=2 | 600=CGY | 604=2 | 605=F7 | 605=CGYU0 | 600=CGZ | 10=209";
+    /// let fix_message = fixparser::FixMessage::from_tag_value(&input).unwrap();
+    /// assert_eq!(Some("AB"), fix_message.get(35));
+    ///
+    /// let legs = fix_message.group(555).unwrap();
+    /// assert_eq!(2, legs.len());
+    /// assert_eq!(Some("CGZ"), legs.instance(1).unwrap().get(600));
+    /// let security_alt_ids = legs.instance(0).unwrap().group(604).unwrap();
+    /// assert_eq!(Some("CGYU0"), security_alt_ids.instance(1).unwrap().get(605));
+    ///
+    /// assert_eq!(vec!["F7", "CGYU0"], fix_message.get_all(605));
+    /// ```
+    pub fn get(&self, tag: i32) -> Option<&str> {
+        self.root().get(tag)
     }
 
-    fn clean(&mut self) {
-        self.pending_tag_indices.clear();
-        self.candidate_indices.clear();
-        self.active_groups.clear();
+    /// Get the values of all the fields with the given tag (including the ones inside repeating
+    /// groups), in the order they appear.
+    ///
+    /// Values which are not valid UTF-8 are skipped.
+    pub fn get_all(&self, tag: i32) -> Vec<&str> {
+        self.root().get_all(tag)
     }
 
-    fn open_group(&mut self, group_delimiter: i32) {
-        debug!("{}INFO: Group detected", self.get_spaces());
-        let group = FixGroup::new(
-            group_delimiter,
-            self.get_index_of_candidate(group_delimiter),
-            self.get_component(),
-        );
-        self.active_groups.push(group);
-        self.candidate_indices.push(HashMap::new());
+    /// Get the repeating group whose NoXXX field has the given tag.
+    ///
+    /// Only the groups of the message are looked up (ie: nested groups are looked up from the
+    /// instances of their parent group).
+    pub fn group(&self, no_tag: i32) -> Option<GroupView<'_>> {
+        self.root().group(no_tag)
     }
 
-    fn get_candidates(&self) -> &HashMap<i32, usize> {
-        self.candidate_indices.last().unwrap()
+    /// Get the values selected by the given path (see [`FixPath`]), in the order they appear.
+    ///
+    /// Values which are not valid UTF-8 are skipped.
+    pub fn query(&self, path: &FixPath) -> Vec<&str> {
+        self.root().query(path)
     }
 
-    fn get_candidates_mut(&mut self) -> &mut HashMap<i32, usize> {
-        self.candidate_indices.last_mut().unwrap()
+    /// Iterates over the fields and groups of the message in the order they appear.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fixparser::EntityView;
+    ///
+    /// let input = "8=FIX.4.4 | 453=1 | 448=A | 10=209";
+    /// let fix_message = fixparser::FixMessage::from_tag_value(&input).unwrap();
+    /// for entity in fix_message.entities() {
+    ///     match entity {
+    ///         EntityView::Field(tag, value) => println!("{}={}", tag, String::from_utf8_lossy(value)),
+    ///         EntityView::Group(group) => println!("group {} ({} instances)", group.no_tag(), group.len()),
+    ///     }
+    /// }
+    /// ```
+    pub fn entities(&self) -> impl Iterator<Item = EntityView<'_>> {
+        self.root().entities()
     }
 
-    fn get_index_of_candidate(&self, tag: i32) -> usize {
-        *self.get_candidates().get(&tag).unwrap()
+    /// Fields and groups of the standard header (eg: BeginString, MsgType, SenderCompID,
+    /// SendingTime), ie: the header tags found at the beginning of the message.
+    ///
+    /// Header tags found after the first field of the body are part of the body (the same goes
+    /// for trailer tags found before the last field of the body).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// let input = "8=FIX.4.4 | 9=5 | 35=D | 49=A | 56=B | 34=12 | 11=order | 93=2 | 89=XX | 10=209";
+    /// let fix_message = fixparser::FixMessage::from_tag_value(&input).unwrap();
+    /// assert_eq!(Some("12"), fix_message.header().get(34));
+    /// assert_eq!(None, fix_message.header().get(11));
+    /// assert_eq!(Some("order"), fix_message.body().get(11));
+    /// assert_eq!(Some("XX"), fix_message.trailer().get(89));
+    /// ```
+    pub fn header(&self) -> ComponentView<'_> {
+        let [header, _body, _trailer] = self.root_component.sections();
+        ComponentView::from_entities(header)
     }
 
-    // must be called before new insertion
-    fn register_candidate(&mut self, tag: i32) {
-        let candidate_index = self.get_entities().len();
-        self.get_candidates_mut().insert(tag, candidate_index);
+    /// Fields and groups of the message which are neither in the standard header nor in the
+    /// standard trailer (see [`FixMessage::header`]).
+    pub fn body(&self) -> ComponentView<'_> {
+        let [_header, body, _trailer] = self.root_component.sections();
+        ComponentView::from_entities(body)
     }
 
-    fn repeated_candidate(&mut self, tag: i32) -> bool {
-        self.get_candidates().contains_key(&tag)
+    /// Fields of the standard trailer (SignatureLength, Signature and CheckSum), ie: the trailer
+    /// tags found at the end of the message (see [`FixMessage::header`]).
+    pub fn trailer(&self) -> ComponentView<'_> {
+        let [_header, _body, trailer] = self.root_component.sections();
+        ComponentView::from_entities(trailer)
     }
 
-    fn get_next_index_of_pending_tag(&self, tag: i32) -> Option<&usize> {
-        self.pending_tag_indices.get(&tag).unwrap().front()
+    /// Get the value of the first field with the given tag, as raw bytes.
+    ///
+    /// Only the fields of the message are looked up (ie: fields inside repeating groups are not).
+    pub fn get_bytes(&self, tag: i32) -> Option<&[u8]> {
+        self.root().get_bytes(tag)
     }
 
-    fn remove_pending_tag(&mut self, tag: i32) {
-        self.pending_tag_indices.get_mut(&tag).unwrap().pop_front();
+    /// Get the value of the first field with the given tag as a string, failing if it is not
+    /// valid UTF-8.
+    ///
+    /// Only the fields of the message are looked up (ie: fields inside repeating groups are not).
+    pub fn get_str(&self, tag: i32) -> Option<Result<&str, Utf8Error>> {
+        self.get_bytes(tag).map(std::str::from_utf8)
     }
 
-    fn close_group(&mut self) {
-        debug!("{}INFO: Stop parsing group\n", self.get_spaces());
-        let closed_group = self.active_groups.pop().unwrap();
-        self.get_component()
-            .entities
-            .push(FixEntity::Group(closed_group));
-        self.candidate_indices.pop();
+    /// Get the value of the first field with the given tag as a string, replacing invalid UTF-8
+    /// sequences with `U+FFFD REPLACEMENT CHARACTER`.
+    ///
+    /// Only the fields of the message are looked up (ie: fields inside repeating groups are not).
+    pub fn get_lossy(&self, tag: i32) -> Option<Cow<'_, str>> {
+        self.get_bytes(tag).map(String::from_utf8_lossy)
     }
 
-    fn is_new_iteration(&self, tag: i32) -> bool {
-        self.is_parsing_group() && tag == self.active_group().delimiter
+    /// Deserializes the message into a type which implements [`serde::Deserialize`].
+    ///
+    /// The message is deserialized as a map whose keys are the tags (see
+    /// [`ComponentView::deserialize_into`]). Strings can borrow from the message.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// #[derive(serde::Deserialize)]
+    /// struct Party {
+    ///     #[serde(rename = "448")]
+    ///     party_id: String,
+    ///     #[serde(rename = "452")]
+    ///     party_role: u32,
+    /// }
+    ///
+    /// #[derive(serde::Deserialize)]
+    /// struct NewOrder<'a> {
+    ///     #[serde(rename = "11")]
+    ///     cl_ord_id: &'a str,
+    ///     #[serde(rename = "38")]
+    ///     order_qty: f64,
+    ///     #[serde(rename = "453")]
+    ///     parties: Vec<Party>,
+    /// }
+    ///
+    /// let input = "8=FIX.4.4 | 35=D | 11=ORDER1 | 38=10 | 453=2 | 448=A | 452=1 | 448=B | 452=3 | 10=000";
+    /// let fix_message = fixparser::FixMessage::from_tag_value(&input).unwrap();
+    /// let new_order: NewOrder = fix_message.deserialize_into().unwrap();
+    /// assert_eq!("ORDER1", new_order.cl_ord_id);
+    /// assert_eq!(3, new_order.parties[1].party_role);
+    /// ```
+    pub fn deserialize_into<'m, T: Deserialize<'m>>(&'m self) -> Result<T, DeserializeError> {
+        self.root().deserialize_into()
     }
 
-    fn increment_iteration(&mut self) {
-        debug!(
-            "{}-- repetition {} --",
-            self.get_spaces(),
-            self.active_group().current_iteration + 1
-        );
-        self.active_group_mut().current_iteration += 1
+    /// Get the value of the first field with the given tag as an integer.
+    ///
+    /// It is `None` if there is no such field, and an error if the value can't be read as an
+    /// integer. The other typed getters behave the same way. Only the fields of the message are
+    /// looked up (ie: fields inside repeating groups are looked up from the group instances).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// let input = "8=FIX.4.4 | 38=10 | 44=2900.25 | 60=20200520-19:15:45.099000 | 1028=N | 10=209";
+    /// let fix_message = fixparser::FixMessage::from_tag_value(&input).unwrap();
+    /// assert_eq!(Some(Ok(10)), fix_message.get_int(38));
+    /// assert_eq!("2900.25", fix_message.get_decimal(44).unwrap().unwrap().to_string());
+    /// assert_eq!(99_000_000, fix_message.get_utc_timestamp(60).unwrap().unwrap().nanosecond);
+    /// assert_eq!(Some(Ok(false)), fix_message.get_bool(1028));
+    /// assert!(fix_message.get_int(8).unwrap().is_err());
+    /// assert_eq!(None, fix_message.get_int(54));
+    /// ```
+    pub fn get_int(&self, tag: i32) -> Option<Result<i64, FieldError>> {
+        self.root().get_int(tag)
     }
 
-    fn get_entities(&mut self) -> &mut Vec<FixEntity> {
-        &mut self.get_component().entities
+    /// Get the value of the first field with the given tag as a [`Decimal`] (see [`FixMessage::get_int`]).
+    pub fn get_decimal(&self, tag: i32) -> Option<Result<Decimal, FieldError>> {
+        self.root().get_decimal(tag)
     }
 
-    fn create_new_group_instance(&mut self) {
-        self.get_candidates_mut().clear();
-        self.increment_iteration();
-        self.active_group_mut().create_new_instance();
+    /// Get the value of the first field with the given tag as a character (see [`FixMessage::get_int`]).
+    pub fn get_char(&self, tag: i32) -> Option<Result<char, FieldError>> {
+        self.root().get_char(tag)
     }
 
-    #[allow(dead_code)]
-    fn get_spaces(&self) -> String {
-        " ".repeat(self.active_groups.len() * 2)
+    /// Get the value of the first field with the given tag as a boolean (see [`FixMessage::get_int`]).
+    pub fn get_bool(&self, tag: i32) -> Option<Result<bool, FieldError>> {
+        self.root().get_bool(tag)
     }
 
-    fn set_known_tag_in_group(&mut self, tag: i32) {
-        self.active_group_mut().insert_known_tag(tag);
+    /// Get the value of the first field with the given tag as a [`UtcTimestamp`] (see [`FixMessage::get_int`]).
+    pub fn get_utc_timestamp(&self, tag: i32) -> Option<Result<UtcTimestamp, FieldError>> {
+        self.root().get_utc_timestamp(tag)
     }
 
-    fn get_component(&mut self) -> &mut FixComponent {
-        if self.is_parsing_group() {
-            self.active_group_mut().instances.last_mut().unwrap()
-        } else {
-            &mut self.root_component
-        }
+    /// Get the value of the first field with the given tag as a [`LocalMktDate`] (see [`FixMessage::get_int`]).
+    pub fn get_local_mkt_date(&self, tag: i32) -> Option<Result<LocalMktDate, FieldError>> {
+        self.root().get_local_mkt_date(tag)
     }
 
-    fn is_parsing_group(&self) -> bool {
-        !self.active_groups.is_empty()
+    /// Get the value of the first field with the given tag as a [`MonthYear`] (see [`FixMessage::get_int`]).
+    pub fn get_month_year(&self, tag: i32) -> Option<Result<MonthYear, FieldError>> {
+        self.root().get_month_year(tag)
     }
 
-    fn active_group(&self) -> &FixGroup {
-        self.active_groups.last().unwrap()
+    /// Get the anomalies found while parsing the message (eg: ignored tokens, missing checksum).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// let input = "8=FIX.4.4 | 9=some | thing=wrong";
+    /// let fix_message = fixparser::FixMessage::from_tag_value(&input).unwrap();
+    /// for warning in fix_message.warnings() {
+    ///     eprintln!("WARNING: {} (offset {})", warning, warning.offset);
+    /// }
+    /// ```
+    pub fn warnings(&self) -> &[ParseWarning] {
+        &self.warnings
     }
 
-    fn active_group_mut(&mut self) -> &mut FixGroup {
-        self.active_groups.last_mut().unwrap()
+    /// Checks the message against the definition of its type (tag 35) in the given dictionary.
+    ///
+    /// The issues are reported in the order they are found: fields and groups not defined for
+    /// the message (or for their group), values which don't match the type of their field or
+    /// which are not one of its enumerated values, NoXXX fields whose value doesn't match the
+    /// number of instances, and missing required fields (after the fields of their component).
+    ///
+    /// Parse the message with [`FixMessage::from_tag_value_with_dictionary`] so that the groups
+    /// are detected as the dictionary defines them (eg: groups with a single instance).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "dictionary")]
+    /// # fn main() {
+    /// # let xml = r#"
+    /// # <fix major="4" minor="4">
+    /// #   <messages>
+    /// #     <message name="NewOrderMultileg" msgtype="AB" msgcat="app">
+    /// #       <field name="Side" required="Y"/>
+    /// #       <group name="NoLegs" required="N"><field name="LegSymbol" required="N"/></group>
+    /// #     </message>
+    /// #   </messages>
+    /// #   <fields>
+    /// #     <field number="35" name="MsgType" type="STRING"/>
+    /// #     <field number="54" name="Side" type="CHAR"/>
+    /// #     <field number="555" name="NoLegs" type="NUMINGROUP"/>
+    /// #     <field number="600" name="LegSymbol" type="STRING"/>
+    /// #   </fields>
+    /// # </fix>"#;
+    /// let dictionary = fixparser::Dictionary::from_quickfix_xml(xml).unwrap();
+    /// let input = "8=FIX.4.4 | 35=AB | 555=3 | 600=QWE | 600=RTY | 10=209";
+    /// let fix_message = fixparser::FixMessage::from_tag_value(&input).unwrap();
+    /// for issue in fix_message.validate(&dictionary) {
+    ///     // Tag 8 is not defined here (8)
+    ///     // Group 555 declares 3 instances, but 2 were found (555)
+    ///     // Tag 10 is not defined here (10)
+    ///     // Required tag 54 is missing (54)
+    ///     println!("{}", issue);
+    /// }
+    /// # }
+    /// # #[cfg(not(feature = "dictionary"))]
+    /// # fn main() {}
+    /// ```
+    pub fn validate(&self, dictionary: &Dictionary) -> Vec<ValidationIssue> {
+        validation::validate(&self.root_component, dictionary)
     }
 
-    fn tag_in_group(&mut self, tag: i32) -> bool {
-        if tag == 10 {
-            eprintln!("WARNING: End of message detected while parsing group");
-            return false;
-        }
-        // from cheaper to more expensive check
-        !self.is_last_iteration()
-            || self.is_known_group_tag(tag)
-            || self.pending_tag_in_last_instance()
+    /// Get the result of the checksum verification.
+    ///
+    /// It is `None` unless the message was parsed with [`ParserOptions::verify_checksum`] and it has a tag 10.
+    pub fn checksum(&self) -> Option<&ChecksumCheck> {
+        self.checksum.as_ref()
     }
 
-    fn pending_tag_in_last_instance(&mut self) -> bool {
-        self.active_group().known_tags.iter().any(|known_tag| {
-            if let Some(tag_index) = self.get_next_index_of_pending_tag(*known_tag) {
-                return self.index_belongs_to_current_group(*tag_index);
-            }
-            false
-        })
+    /// Get the result of the body length verification.
+    ///
+    /// It is `None` unless the message was parsed with [`ParserOptions::verify_body_length`] and it has a tag 9.
+    pub fn body_length(&self) -> Option<&BodyLengthCheck> {
+        self.body_length.as_ref()
+    }
+
+    /// Sets the value of the first field with the given tag. If there is no such field, it is
+    /// added before the CheckSum. Returns `false` (and nothing is changed) if the tag is the NoXXX
+    /// field of a group, whose count can only change by adding or removing instances.
+    ///
+    /// Only the fields of the message are looked up (ie: fields inside repeating groups are
+    /// changed from the group instances). BodyLength and CheckSum are not updated, but they are
+    /// recomputed by [`FixMessage::to_tag_value`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// let input = "8=FIX.4.4 | 35=D | 11=ORDER1 | 453=2 | 448=A | 448=B | 10=000";
+    /// let mut fix_message = fixparser::FixMessage::from_tag_value(&input).unwrap();
+    /// fix_message.set(11, "ORDER2");
+    /// fix_message.insert_after(11, 52, "20200520-19:15:45.134");
+    /// fix_message.push_group_instance(453).unwrap().set(448, "C");
+    /// assert_eq!(Some("ORDER2"), fix_message.get(11));
+    /// assert_eq!(vec!["A", "B", "C"], fix_message.get_all(448));
+    /// assert_eq!(3, fix_message.group(453).unwrap().declared_len());
+    /// ```
+    pub fn set(&mut self, tag: i32, value: impl Into<Vec<u8>>) -> bool {
+        self.root_mut().set(tag, value)
+    }
+
+    /// Removes the first field with the given tag (or the group whose NoXXX field has the given
+    /// tag). Returns `false` if there is no such field or group.
+    pub fn remove(&mut self, tag: i32) -> bool {
+        self.root_mut().remove(tag)
+    }
+
+    /// Inserts a field after the first field with the tag `anchor_tag` (or after the group whose
+    /// NoXXX field has that tag). Returns `false` if the anchor is not found.
+    pub fn insert_after(&mut self, anchor_tag: i32, tag: i32, value: impl Into<Vec<u8>>) -> bool {
+        self.root_mut().insert_after(anchor_tag, tag, value)
+    }
+
+    /// Appends an empty instance to the group whose NoXXX field has the given tag, and returns it
+    /// so its fields can be set. The NoXXX count is updated accordingly, so the instance is counted
+    /// (and encoded as nothing) even if none of its fields is set.
+    ///
+    /// It is `None` if there is no such group. Note that a group with a single instance is parsed
+    /// as plain fields without a dictionary (see [`FixMessage::from_tag_value_with_dictionary`]),
+    /// so it is not found.
+    pub fn push_group_instance(&mut self, no_tag: i32) -> Option<ComponentMut<'_>> {
+        view_mut::push_group_instance(&mut self.root_component, no_tag)
+    }
+
+    /// Removes the instance at the given index (starting at 0) of the group whose NoXXX field has
+    /// the given tag. The NoXXX count is updated accordingly.
+    ///
+    /// Returns `false` if there is no such group or instance.
+    pub fn remove_group_instance(&mut self, no_tag: i32, index: usize) -> bool {
+        self.root_mut().remove_group_instance(no_tag, index)
     }
 
-    fn index_belongs_to_current_group(&self, tag_index: usize) -> bool {
-        if let Some(delimiter_index) =
-            self.get_next_index_of_pending_tag(self.active_group().delimiter)
-        {
-            return tag_index < *delimiter_index;
-        }
-        true
+    /// Get the instance at the given index (starting at 0) of the group whose NoXXX field has the
+    /// given tag, so its fields can be changed.
+    pub fn group_instance_mut(&mut self, no_tag: i32, index: usize) -> Option<ComponentMut<'_>> {
+        view_mut::group_instance_mut(&mut self.root_component, no_tag, index)
     }
 
-    fn is_known_group_tag(&self, tag: i32) -> bool {
-        self.active_group().known_tags.contains(&tag)
+    fn root(&self) -> ComponentView<'_> {
+        ComponentView::new(&self.root_component)
     }
 
-    fn is_last_iteration(&self) -> bool {
-        self.active_group().current_iteration == self.active_group().repetitions
+    fn root_mut(&mut self) -> ComponentMut<'_> {
+        ComponentMut::new(&mut self.root_component)
     }
 }
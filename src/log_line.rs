@@ -0,0 +1,175 @@
+use crate::LogFormatError;
+use regex::Regex;
+
+const CAPTURE_NAMES: [&str; 3] = ["timestamp", "direction", "session_id"];
+
+/// Whether a message was received or sent, according to the prefix of its log line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Incoming,
+    Outgoing,
+}
+
+impl Direction {
+    // eg: Recv, IN, incoming, < (case insensitive)
+    fn from_log(direction: &str) -> Option<Self> {
+        match direction.to_ascii_lowercase().as_str() {
+            "in" | "incoming" | "inbound" | "recv" | "received" | "receive" | "<" | "<<" => {
+                Some(Direction::Incoming)
+            }
+            "out" | "outgoing" | "outbound" | "sent" | "send" | ">" | ">>" => {
+                Some(Direction::Outgoing)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// The text found before the message in a log line, and the metadata recognized in it.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct LogPrefix {
+    /// Everything before the BeginString (eg: `20200520-19:15:45.134 Recv FIX.4.4:A->B `).
+    pub raw: String,
+    /// Format of the log line which recognized the prefix (`None` if no format did).
+    pub format: Option<String>,
+    pub timestamp: Option<String>,
+    /// `None` if the format has no direction or if it is not a known word (eg: `Recv`, `OUT`).
+    pub direction: Option<Direction>,
+    pub session_id: Option<String>,
+}
+
+/// A format of log lines, given by a regex which is matched against the text found before the
+/// message (see [`FixMessage::from_log_line`](crate::FixMessage::from_log_line)).
+///
+/// The metadata is taken from the named captures `timestamp`, `direction` and `session_id`. The
+/// regex should be anchored (ie: `^...$`), as the prefix is recognized by the first format
+/// which matches it.
+///
+/// # Example
+///
+/// ```rust
+/// use fixparser::{Direction, FixMessage, LogFormat};
+///
+/// let format = LogFormat::new(
+///     "engine",
+///     r"^(?P<timestamp>\S+) (?P<direction>Recv|Sent) (?P<session_id>\S+) $",
+/// )
+/// .unwrap();
+/// let line = "20200520-19:15:45.134 Recv FIX.4.4:A->B 8=FIX.4.4|35=0|10=209";
+/// let (prefix, fix_message) = FixMessage::from_log_line(line, &[format]).unwrap();
+/// assert_eq!(Some("20200520-19:15:45.134"), prefix.timestamp.as_deref());
+/// assert_eq!(Some(Direction::Incoming), prefix.direction);
+/// assert_eq!(Some("FIX.4.4:A->B"), prefix.session_id.as_deref());
+/// assert_eq!(Some("0"), fix_message.get(35));
+/// ```
+#[derive(Debug, Clone)]
+pub struct LogFormat {
+    name: String,
+    regex: Regex,
+}
+
+impl LogFormat {
+    /// Creates a format with the given name (eg: the name of the engine) and regex.
+    ///
+    /// # Errors
+    ///
+    /// A [`LogFormatError`] is returned if the regex is not valid or if it has a named capture
+    /// other than `timestamp`, `direction` and `session_id`.
+    pub fn new(name: &str, pattern: &str) -> Result<Self, LogFormatError> {
+        let regex = Regex::new(pattern).map_err(|error| LogFormatError::InvalidRegex {
+            message: error.to_string(),
+        })?;
+        if let Some(name) = regex
+            .capture_names()
+            .flatten()
+            .find(|name| !CAPTURE_NAMES.contains(name))
+        {
+            return Err(LogFormatError::UnknownCapture {
+                name: name.to_string(),
+            });
+        }
+        Ok(Self {
+            name: name.to_string(),
+            regex,
+        })
+    }
+
+    /// Name of the format.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Formats of the message logs of QuickFIX/J, QuickFIX/n, OnixS and Fix8 (see their
+    /// constructors), in that order.
+    pub fn builtin() -> Vec<LogFormat> {
+        vec![
+            Self::quickfixj(),
+            Self::quickfixn(),
+            Self::onixs(),
+            Self::fix8(),
+        ]
+    }
+
+    /// QuickFIX/J file log: `20200520-19:15:45.134: 8=FIX.4.4|...` (the session is the name of
+    /// the file).
+    pub fn quickfixj() -> Self {
+        Self::builtin_format(
+            "QuickFIX/J",
+            r"^(?P<timestamp>\d{8}-\d{2}:\d{2}:\d{2}(\.\d+)?): $",
+        )
+    }
+
+    /// QuickFIX/n file log: `20200520-19:15:45.134 : 8=FIX.4.4|...` (the session is the name of
+    /// the file).
+    pub fn quickfixn() -> Self {
+        Self::builtin_format(
+            "QuickFIX/n",
+            r"^(?P<timestamp>\d{8}-\d{2}:\d{2}:\d{2}(\.\d+)?) : $",
+        )
+    }
+
+    /// OnixS session log: `20200520-19:15:45.134 IN  8=FIX.4.4|...` (or `OUT`).
+    pub fn onixs() -> Self {
+        Self::builtin_format(
+            "OnixS",
+            r"^(?P<timestamp>\d{8}-\d{2}:\d{2}:\d{2}(\.\d+)?)\s+(?P<direction>IN|OUT)\s+$",
+        )
+    }
+
+    /// Fix8 protocol log: `2020-05-20 19:15:45.134000000 in  1 8=FIX.4.4|...` (or `out`, followed
+    /// by the sequence number).
+    pub fn fix8() -> Self {
+        Self::builtin_format(
+            "Fix8",
+            r"^(?P<timestamp>\d{4}-\d{2}-\d{2} \d{2}:\d{2}:\d{2}(\.\d+)?)\s+(?P<direction>in|out)\s+\d+\s+$",
+        )
+    }
+
+    fn builtin_format(name: &str, pattern: &str) -> Self {
+        Self::new(name, pattern).expect("The built-in formats are valid")
+    }
+
+    // metadata of the prefix if it matches the format
+    fn recognize(&self, prefix: &str) -> Option<LogPrefix> {
+        let captures = self.regex.captures(prefix)?;
+        let capture = |name| captures.name(name).map(|capture| capture.as_str());
+        Some(LogPrefix {
+            raw: prefix.to_string(),
+            format: Some(self.name.clone()),
+            timestamp: capture("timestamp").map(String::from),
+            direction: capture("direction").and_then(Direction::from_log),
+            session_id: capture("session_id").map(String::from),
+        })
+    }
+}
+
+// metadata of the prefix according to the first format which recognizes it
+pub(crate) fn recognize_prefix(prefix: &str, formats: &[LogFormat]) -> LogPrefix {
+    formats
+        .iter()
+        .find_map(|format| format.recognize(prefix))
+        .unwrap_or_else(|| LogPrefix {
+            raw: prefix.to_string(),
+            ..LogPrefix::default()
+        })
+}
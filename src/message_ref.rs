@@ -0,0 +1,244 @@
+use crate::model::FixComponent;
+use crate::parser::Parser;
+use crate::{json, tag_value, validation, view};
+use crate::{
+    BodyLengthCheck, ChecksumCheck, ComponentView, Decimal, DeserializeError, Dictionary,
+    EntityView, FieldError, FixMessage, FixPath, GroupView, LocalMktDate, MonthYear, ParseError,
+    ParseWarning, ParserOptions, UtcTimestamp, ValidationIssue,
+};
+use serde::{Deserialize, Serialize, Serializer};
+use std::borrow::Cow;
+use std::str::Utf8Error;
+
+/// A FIX message whose values borrow from the input (ie: no allocation is made per field).
+///
+/// It detects groups and it is serialized to json as [`FixMessage`]. Use [`FixMessageRef::to_owned`]
+/// if the message needs to outlive the input.
+///
+/// # Example
+///
+/// ```rust
+/// let input = String::from("8=FIX.4.4 | 555=2 | 600=CGY | 600=CGZ | 10=209");
+/// let fix_message = fixparser::FixMessageRef::from_tag_value(&input).unwrap();
+/// assert_eq!(Some(&b"FIX.4.4"[..]), fix_message.get_bytes(8));
+/// println!("{}", fix_message.to_json());
+/// ```
+pub struct FixMessageRef<'a> {
+    root_component: FixComponent<&'a [u8]>,
+    warnings: Vec<ParseWarning>,
+    checksum: Option<ChecksumCheck>,
+    body_length: Option<BodyLengthCheck>,
+}
+
+/// Same as [`FixMessage`]'s implementation.
+impl<'a> Serialize for FixMessageRef<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.root_component.serialize(serializer)
+    }
+}
+
+impl<'a> FixMessageRef<'a> {
+    /// Same as [`FixMessage::from_tag_value`], but the values borrow from the input.
+    pub fn from_tag_value(input_message: &'a str) -> Result<FixMessageRef<'a>, ParseError> {
+        Self::from_bytes_with(input_message.as_bytes(), &ParserOptions::default())
+    }
+
+    /// Same as [`FixMessage::from_tag_value_with`], but the values borrow from the input.
+    pub fn from_tag_value_with(
+        input_message: &'a str,
+        options: &ParserOptions,
+    ) -> Result<FixMessageRef<'a>, ParseError> {
+        Self::from_bytes_with(input_message.as_bytes(), options)
+    }
+
+    /// Same as [`FixMessage::from_bytes`], but the values borrow from the input.
+    pub fn from_bytes(input_message: &'a [u8]) -> Result<FixMessageRef<'a>, ParseError> {
+        Self::from_bytes_with(input_message, &ParserOptions::default())
+    }
+
+    /// Same as [`FixMessage::from_bytes_with`], but the values borrow from the input.
+    pub fn from_bytes_with(
+        input_message: &'a [u8],
+        options: &ParserOptions,
+    ) -> Result<FixMessageRef<'a>, ParseError> {
+        let parsed_message = Parser::parse(input_message, options)?;
+        Ok(Self {
+            root_component: parsed_message.root_component,
+            warnings: parsed_message.warnings,
+            checksum: parsed_message.checksum,
+            body_length: parsed_message.body_length,
+        })
+    }
+
+    /// Creates a [`FixMessage`] which owns a copy of the values.
+    pub fn to_owned(&self) -> FixMessage {
+        FixMessage {
+            root_component: self.root_component.to_owned(),
+            warnings: self.warnings.clone(),
+            checksum: self.checksum.clone(),
+            body_length: self.body_length.clone(),
+        }
+    }
+
+    /// Same as [`FixMessage::to_json`].
+    pub fn to_json(&self) -> serde_json::value::Value {
+        serde_json::json!(&self.root_component)
+    }
+
+    /// Same as [`FixMessage::to_json_with_sections`].
+    pub fn to_json_with_sections(&self) -> serde_json::value::Value {
+        json::component_to_sectioned_json(&self.root_component)
+    }
+
+    /// Same as [`FixMessage::to_json_with_names`].
+    pub fn to_json_with_names(&self, dictionary: &Dictionary) -> serde_json::value::Value {
+        json::component_to_named_json(&self.root_component, dictionary, false)
+    }
+
+    /// Same as [`FixMessage::to_annotated_json`].
+    pub fn to_annotated_json(&self, dictionary: &Dictionary) -> serde_json::value::Value {
+        json::component_to_named_json(&self.root_component, dictionary, true)
+    }
+
+    /// Same as [`FixMessage::validate`].
+    pub fn validate(&self, dictionary: &Dictionary) -> Vec<ValidationIssue> {
+        validation::validate(&self.root_component, dictionary)
+    }
+
+    /// Same as [`FixMessage::get`], but the value borrows from the input.
+    pub fn get(&self, tag: i32) -> Option<&'a str> {
+        self.get_bytes(tag)
+            .and_then(|value| std::str::from_utf8(value).ok())
+    }
+
+    /// Same as [`FixMessage::to_tag_value`].
+    pub fn to_tag_value(&self) -> Vec<u8> {
+        self.to_tag_value_with(tag_value::SOH)
+    }
+
+    /// Same as [`FixMessage::to_tag_value_with`].
+    pub fn to_tag_value_with(&self, separator: &[u8]) -> Vec<u8> {
+        tag_value::encode(&self.root_component, separator)
+    }
+
+    /// Same as [`FixMessage::get_all`], but the values borrow from the input.
+    pub fn get_all(&self, tag: i32) -> Vec<&'a str> {
+        view::field_values(&self.root_component.entities, tag)
+            .into_iter()
+            .filter_map(|value| std::str::from_utf8(value).ok())
+            .collect()
+    }
+
+    /// Same as [`FixMessage::group`].
+    pub fn group(&self, no_tag: i32) -> Option<GroupView<'_, &'a [u8]>> {
+        self.root().group(no_tag)
+    }
+
+    /// Same as [`FixMessage::query`], but the values borrow from the input.
+    pub fn query(&self, path: &FixPath) -> Vec<&'a str> {
+        path.evaluate(&self.root_component.entities)
+            .into_iter()
+            .filter_map(|value| std::str::from_utf8(value).ok())
+            .collect()
+    }
+
+    /// Same as [`FixMessage::entities`].
+    pub fn entities(&self) -> impl Iterator<Item = EntityView<'_, &'a [u8]>> {
+        self.root().entities()
+    }
+
+    /// Same as [`FixMessage::header`].
+    pub fn header(&self) -> ComponentView<'_, &'a [u8]> {
+        let [header, _body, _trailer] = self.root_component.sections();
+        ComponentView::from_entities(header)
+    }
+
+    /// Same as [`FixMessage::body`].
+    pub fn body(&self) -> ComponentView<'_, &'a [u8]> {
+        let [_header, body, _trailer] = self.root_component.sections();
+        ComponentView::from_entities(body)
+    }
+
+    /// Same as [`FixMessage::trailer`].
+    pub fn trailer(&self) -> ComponentView<'_, &'a [u8]> {
+        let [_header, _body, trailer] = self.root_component.sections();
+        ComponentView::from_entities(trailer)
+    }
+
+    /// Same as [`FixMessage::get_bytes`], but the value borrows from the input.
+    pub fn get_bytes(&self, tag: i32) -> Option<&'a [u8]> {
+        self.root_component.get_field(tag).copied()
+    }
+
+    /// Same as [`FixMessage::get_str`], but the value borrows from the input.
+    pub fn get_str(&self, tag: i32) -> Option<Result<&'a str, Utf8Error>> {
+        self.get_bytes(tag).map(std::str::from_utf8)
+    }
+
+    /// Same as [`FixMessage::get_lossy`], but the value borrows from the input (if it is valid UTF-8).
+    pub fn get_lossy(&self, tag: i32) -> Option<Cow<'a, str>> {
+        self.get_bytes(tag).map(String::from_utf8_lossy)
+    }
+
+    /// Same as [`FixMessage::deserialize_into`].
+    pub fn deserialize_into<'m, T: Deserialize<'m>>(&'m self) -> Result<T, DeserializeError> {
+        self.root().deserialize_into()
+    }
+
+    /// Same as [`FixMessage::get_int`].
+    pub fn get_int(&self, tag: i32) -> Option<Result<i64, FieldError>> {
+        self.root().get_int(tag)
+    }
+
+    /// Same as [`FixMessage::get_decimal`].
+    pub fn get_decimal(&self, tag: i32) -> Option<Result<Decimal, FieldError>> {
+        self.root().get_decimal(tag)
+    }
+
+    /// Same as [`FixMessage::get_char`].
+    pub fn get_char(&self, tag: i32) -> Option<Result<char, FieldError>> {
+        self.root().get_char(tag)
+    }
+
+    /// Same as [`FixMessage::get_bool`].
+    pub fn get_bool(&self, tag: i32) -> Option<Result<bool, FieldError>> {
+        self.root().get_bool(tag)
+    }
+
+    /// Same as [`FixMessage::get_utc_timestamp`].
+    pub fn get_utc_timestamp(&self, tag: i32) -> Option<Result<UtcTimestamp, FieldError>> {
+        self.root().get_utc_timestamp(tag)
+    }
+
+    /// Same as [`FixMessage::get_local_mkt_date`].
+    pub fn get_local_mkt_date(&self, tag: i32) -> Option<Result<LocalMktDate, FieldError>> {
+        self.root().get_local_mkt_date(tag)
+    }
+
+    /// Same as [`FixMessage::get_month_year`].
+    pub fn get_month_year(&self, tag: i32) -> Option<Result<MonthYear, FieldError>> {
+        self.root().get_month_year(tag)
+    }
+
+    /// Same as [`FixMessage::warnings`].
+    pub fn warnings(&self) -> &[ParseWarning] {
+        &self.warnings
+    }
+
+    /// Same as [`FixMessage::checksum`].
+    pub fn checksum(&self) -> Option<&ChecksumCheck> {
+        self.checksum.as_ref()
+    }
+
+    /// Same as [`FixMessage::body_length`].
+    pub fn body_length(&self) -> Option<&BodyLengthCheck> {
+        self.body_length.as_ref()
+    }
+
+    fn root(&self) -> ComponentView<'_, &'a [u8]> {
+        ComponentView::new(&self.root_component)
+    }
+}
@@ -0,0 +1,209 @@
+use serde::{ser::SerializeMap, Serialize, Serializer};
+use std::collections::HashSet;
+
+#[derive(Debug, Clone)]
+pub(crate) enum FixEntity<V> {
+    Field(i32, V),
+    Group(FixGroup<V>),
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct FixComponent<V> {
+    pub(crate) entities: Vec<FixEntity<V>>,
+}
+
+impl<V> FixComponent<V> {
+    pub(crate) fn new(entities: Vec<FixEntity<V>>) -> Self {
+        Self { entities }
+    }
+
+    // value of the first field with the given tag (fields inside groups are not looked up)
+    pub(crate) fn get_field(&self, tag: i32) -> Option<&V> {
+        get_field(&self.entities, tag)
+    }
+
+    // entities of the standard header, the body and the standard trailer of a message. The header
+    // is made of the header tags found at the beginning of the message, and the trailer of the
+    // trailer tags found at the end (the ones found elsewhere are part of the body)
+    pub(crate) fn sections(&self) -> [&[FixEntity<V>]; 3] {
+        let is_header = |entity: &FixEntity<V>| HEADER_TAGS.contains(&entity.tag());
+        let is_trailer = |entity: &FixEntity<V>| TRAILER_TAGS.contains(&entity.tag());
+        let header_end = self
+            .entities
+            .iter()
+            .position(|entity| !is_header(entity))
+            .unwrap_or(self.entities.len());
+        let trailer_length = self.entities[header_end..]
+            .iter()
+            .rev()
+            .take_while(|entity| is_trailer(entity))
+            .count();
+        let (header, rest) = self.entities.split_at(header_end);
+        let (body, trailer) = rest.split_at(rest.len() - trailer_length);
+        [header, body, trailer]
+    }
+}
+
+// tags of the StandardHeader component (FIX 4.4 and FIXT 1.1)
+const HEADER_TAGS: [i32; 30] = [
+    8, 9, 35, 1128, 1156, 1129, 49, 56, 115, 128, 90, 91, 34, 50, 142, 57, 143, 116, 144, 129, 145,
+    43, 97, 52, 122, 212, 213, 347, 369, 627,
+];
+// tags of the StandardTrailer component
+const TRAILER_TAGS: [i32; 3] = [93, 89, 10];
+
+impl<V> FixEntity<V> {
+    // tag of the field, or tag of the NoXXX field for groups
+    pub(crate) fn tag(&self) -> i32 {
+        match self {
+            FixEntity::Field(tag, _value) => *tag,
+            FixEntity::Group(group) => group.no_tag,
+        }
+    }
+}
+
+pub(crate) fn get_field<V>(entities: &[FixEntity<V>], tag: i32) -> Option<&V> {
+    entities.iter().find_map(|entity| match entity {
+        FixEntity::Field(field_tag, value) if *field_tag == tag => Some(value),
+        _ => None,
+    })
+}
+
+impl FixComponent<&[u8]> {
+    pub(crate) fn to_owned(&self) -> FixComponent<Vec<u8>> {
+        FixComponent::new(
+            self.entities
+                .iter()
+                .map(|entity| match entity {
+                    FixEntity::Field(tag, value) => FixEntity::Field(*tag, value.to_vec()),
+                    FixEntity::Group(group) => FixEntity::Group(group.to_owned()),
+                })
+                .collect(),
+        )
+    }
+}
+
+impl<V: AsRef<[u8]>> Serialize for FixComponent<V> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize_entities(&self.entities, serializer)
+    }
+}
+
+// a map whose keys are the tags and whose values are the values of the fields or the instances of
+// the groups
+pub(crate) fn serialize_entities<V: AsRef<[u8]>, S: Serializer>(
+    entities: &[FixEntity<V>],
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    let mut map = serializer.serialize_map(Some(entities.len()))?;
+    for entity in entities {
+        match entity {
+            FixEntity::Field(ref tag, ref value) => {
+                map.serialize_entry(tag, &String::from_utf8_lossy(value.as_ref()))?;
+            }
+            FixEntity::Group(ref group) => {
+                map.serialize_entry(&group.no_tag, &group.instances)?;
+            }
+        }
+    }
+    map.end()
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct FixGroup<V> {
+    pub(crate) delimiter: i32, // first tag of each group instance
+    pub(crate) no_tag: i32,    // tag which contains the number of repetitions
+    pub(crate) repetitions: i32,
+    pub(crate) current_iteration: i32,
+    pub(crate) known_tags: HashSet<i32>, // tags we know that belong to this group
+    pub(crate) instances: Vec<FixComponent<V>>,
+}
+
+impl<V: AsRef<[u8]>> FixGroup<V> {
+    // the NoXXX field is the one before the first delimiter
+    pub(crate) fn new(
+        delimiter: i32,
+        no_tag: i32,
+        repetitions: i32,
+        index_first_delimiter: usize,
+        component: &mut FixComponent<V>,
+    ) -> Self {
+        let group_instance =
+            FixComponent::new(component.entities.drain(index_first_delimiter..).collect());
+        component.entities.pop();
+
+        Self {
+            no_tag, // bad variable name, as in FIX
+            delimiter,
+            repetitions,
+            current_iteration: 1,
+            known_tags: Self::get_known_tags(&group_instance),
+            instances: vec![group_instance],
+        }
+    }
+
+    // group created from its instances (ie: not while parsing)
+    pub(crate) fn from_instances(no_tag: i32, instances: Vec<FixComponent<V>>) -> Self {
+        let delimiter = instances
+            .first()
+            .and_then(|instance| instance.entities.first())
+            .map_or(0, |entity| match entity {
+                FixEntity::Field(tag, _value) => *tag,
+                FixEntity::Group(group) => group.no_tag,
+            });
+        let mut known_tags = HashSet::new();
+        instances
+            .iter()
+            .for_each(|instance| known_tags.extend(Self::get_known_tags(instance)));
+        Self {
+            no_tag,
+            delimiter,
+            repetitions: instances.len() as i32,
+            current_iteration: instances.len() as i32,
+            known_tags,
+            instances,
+        }
+    }
+
+    fn get_known_tags(group_instance: &FixComponent<V>) -> HashSet<i32> {
+        let mut known_tags = HashSet::<i32>::new();
+        group_instance
+            .entities
+            .iter()
+            .for_each(|entity| match entity {
+                FixEntity::Field(tag, _value) => {
+                    known_tags.insert(*tag);
+                }
+                FixEntity::Group(group) => {
+                    group.known_tags.iter().for_each(|known_tag| {
+                        known_tags.insert(*known_tag);
+                    });
+                }
+            });
+        known_tags
+    }
+
+    pub(crate) fn create_new_instance(&mut self) {
+        self.instances.push(FixComponent::new(Vec::new()));
+    }
+
+    pub(crate) fn insert_known_tag(&mut self, tag: i32) {
+        self.known_tags.insert(tag);
+    }
+}
+
+impl FixGroup<&[u8]> {
+    fn to_owned(&self) -> FixGroup<Vec<u8>> {
+        FixGroup {
+            delimiter: self.delimiter,
+            no_tag: self.no_tag,
+            repetitions: self.repetitions,
+            current_iteration: self.current_iteration,
+            known_tags: self.known_tags.clone(),
+            instances: self.instances.iter().map(FixComponent::to_owned).collect(),
+        }
+    }
+}
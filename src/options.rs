@@ -0,0 +1,175 @@
+use crate::data_fields::DataFields;
+use crate::WarningKind;
+
+/// Options to customize how messages are parsed.
+///
+/// By default, the parser is lenient: the anomalies found in a message are reported as warnings
+/// (see [`FixMessage::warnings`](crate::FixMessage::warnings)) and the message is parsed anyway.
+/// Some of them (or all of them, see [`ParserOptions::strict`]) can be rejected with
+/// [`ParseError::Rejected`](crate::ParseError::Rejected) instead.
+///
+/// # Example
+///
+/// ```rust
+/// use fixparser::{FixMessage, ParseError, ParserOptions};
+///
+/// let options = ParserOptions::new().verify_checksum(true);
+/// let input = "8=FIX.4.4 | 9=5 | 35=0 | 10=163";
+/// let fix_message = FixMessage::from_tag_value_with(input, &options).unwrap();
+/// assert!(fix_message.checksum().unwrap().is_valid());
+///
+/// let input = "8=FIX.4.4 | 9=5 | 35=0 | 10=163 | 11=after checksum";
+/// assert!(FixMessage::from_tag_value(input).is_ok());
+/// let fix_message = FixMessage::from_tag_value_with(input, &ParserOptions::strict());
+/// assert!(matches!(fix_message, Err(ParseError::Rejected { .. })));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ParserOptions {
+    pub(crate) verify_checksum: bool,
+    pub(crate) verify_body_length: bool,
+    pub(crate) data_fields: DataFields,
+    pub(crate) separators: Vec<Vec<u8>>, // the separator is detected if empty
+    pub(crate) require_standard_header: bool,
+    pub(crate) reject_duplicate_tags: bool,
+    pub(crate) require_checksum: bool,
+    pub(crate) reject_empty_values: bool,
+    pub(crate) reject_invalid_groups: bool,
+    pub(crate) reject_all_warnings: bool,
+}
+
+impl ParserOptions {
+    /// Creates the default options (the ones used by `FixMessage::from_tag_value`).
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Same as [`ParserOptions::new`]: the anomalies are reported as warnings, but the message is
+    /// parsed anyway (eg: invalid tags are skipped, the checksum is optional and the fields after
+    /// it are ignored). Useful to read messages as they are (eg: in a log viewer).
+    pub fn lenient() -> Self {
+        Self::new()
+    }
+
+    /// Verifies the checksum and the body length, enables all the checks below and rejects the
+    /// message on any warning. Useful for conformance tests.
+    pub fn strict() -> Self {
+        Self::new()
+            .verify_checksum(true)
+            .verify_body_length(true)
+            .require_standard_header(true)
+            .reject_duplicate_tags(true)
+            .require_checksum(true)
+            .reject_empty_values(true)
+            .reject_invalid_groups(true)
+            .reject_all_warnings(true)
+    }
+
+    /// Computes the checksum of the message and compares it with the value of tag 10.
+    ///
+    /// If the separator is not SOH, the checksum is computed as if SOH had been used.
+    pub fn verify_checksum(mut self, verify_checksum: bool) -> Self {
+        self.verify_checksum = verify_checksum;
+        self
+    }
+
+    /// Measures the body of the message (from the end of tag 9 to the beginning of tag 10) and
+    /// compares it with the value of tag 9.
+    ///
+    /// If the separator is not SOH, each separator is counted as a single byte.
+    pub fn verify_body_length(mut self, verify_body_length: bool) -> Self {
+        self.verify_body_length = verify_body_length;
+        self
+    }
+
+    /// Registers a custom data field, so that the value of `data_tag` is read using the length
+    /// given in `length_tag` (eg: it can contain the separator).
+    ///
+    /// The data fields defined by the FIX standard (eg: RawData, XmlData) are always registered.
+    pub fn data_field(mut self, length_tag: i32, data_tag: i32) -> Self {
+        self.data_fields.insert(length_tag, data_tag);
+        self
+    }
+
+    /// Uses the given separator instead of detecting it (eg: `"|"`, `"\x01"`, `"^A"` or the
+    /// literal `"\\x01"`). The whitespace around it is ignored (eg: ` | `).
+    ///
+    /// If the separator is not found after the BeginString, [`ParseError::MissingSeparator`] is
+    /// returned.
+    ///
+    /// [`ParseError::MissingSeparator`]: crate::ParseError::MissingSeparator
+    pub fn separator<S: AsRef<[u8]>>(self, separator: S) -> Self {
+        self.allowed_separators(&[separator])
+    }
+
+    /// Uses the first of the given separators found after the BeginString (eg: `&["\x01", "|"]`).
+    /// An empty list detects the separator (the default).
+    ///
+    /// The separator is detected as the non-alphanumeric bytes between the BeginString and the
+    /// second tag (eg: SOH, `|`, `;`), or as the caret notation `^A` or the literal `\x01` if
+    /// they are found there.
+    pub fn allowed_separators<S: AsRef<[u8]>>(mut self, separators: &[S]) -> Self {
+        self.separators = separators
+            .iter()
+            .map(|separator| separator.as_ref().to_vec())
+            .collect();
+        self
+    }
+
+    /// Rejects the message if BeginString (8), BodyLength (9) and MsgType (35) are not its first
+    /// three tags.
+    pub fn require_standard_header(mut self, require_standard_header: bool) -> Self {
+        self.require_standard_header = require_standard_header;
+        self
+    }
+
+    /// Rejects the message if a tag is repeated outside a repeating group (or in more instances
+    /// than the NoXXX field of the group declares).
+    pub fn reject_duplicate_tags(mut self, reject_duplicate_tags: bool) -> Self {
+        self.reject_duplicate_tags = reject_duplicate_tags;
+        self
+    }
+
+    /// Rejects the message if it has no CheckSum (tag 10).
+    pub fn require_checksum(mut self, require_checksum: bool) -> Self {
+        self.require_checksum = require_checksum;
+        self
+    }
+
+    /// Rejects the message if a field has no value (eg: `58=`).
+    pub fn reject_empty_values(mut self, reject_empty_values: bool) -> Self {
+        self.reject_empty_values = reject_empty_values;
+        self
+    }
+
+    /// Rejects the message if the NoXXX field of a repeating group is not a number of repetitions
+    /// ([`ParseError::InvalidGroupCount`]) or if a repeated tag has no field before it which could
+    /// be the NoXXX one ([`ParseError::MissingGroupCount`]).
+    ///
+    /// [`ParseError::InvalidGroupCount`]: crate::ParseError::InvalidGroupCount
+    /// [`ParseError::MissingGroupCount`]: crate::ParseError::MissingGroupCount
+    pub fn reject_invalid_groups(mut self, reject_invalid_groups: bool) -> Self {
+        self.reject_invalid_groups = reject_invalid_groups;
+        self
+    }
+
+    /// Rejects the message on any warning (eg: ignored tokens, fields after the checksum or a
+    /// checksum mismatch if it is verified).
+    pub fn reject_all_warnings(mut self, reject_all_warnings: bool) -> Self {
+        self.reject_all_warnings = reject_all_warnings;
+        self
+    }
+
+    pub(crate) fn rejects(&self, kind: &WarningKind) -> bool {
+        self.reject_all_warnings
+            || match kind {
+                WarningKind::MisplacedHeaderTag { .. } => self.require_standard_header,
+                WarningKind::DuplicateTag => self.reject_duplicate_tags,
+                WarningKind::MissingChecksum => self.require_checksum,
+                WarningKind::EmptyValue => self.reject_empty_values,
+                WarningKind::InvalidGroupCount { .. } | WarningKind::MissingGroupCount => {
+                    self.reject_invalid_groups
+                }
+                _ => false,
+            }
+    }
+}
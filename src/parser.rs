@@ -0,0 +1,785 @@
+use crate::model::{FixComponent, FixEntity, FixGroup};
+use crate::verification::{self, FieldsSummary};
+use crate::{
+    BodyLengthCheck, ChecksumCheck, Dictionary, GroupDef, MessageDef, ParseError, ParseWarning,
+    ParserOptions, WarningKind,
+};
+use std::collections::{HashMap, VecDeque};
+
+#[derive(Debug)]
+struct TagValue<'a> {
+    tag: i32,
+    value: &'a [u8],
+    offset: usize,   // offset of the tag in the input
+    token: &'a [u8], // tag=value as found in the input
+}
+
+// result of parsing a message
+pub(crate) struct ParsedMessage<V> {
+    pub(crate) root_component: FixComponent<V>,
+    pub(crate) warnings: Vec<ParseWarning>,
+    pub(crate) checksum: Option<ChecksumCheck>,
+    pub(crate) body_length: Option<BodyLengthCheck>,
+}
+
+// V is the type of the values of the fields (eg: owned or borrowed from the input)
+pub(crate) struct Parser<'d, V> {
+    root_component: FixComponent<V>,
+    pending_tag_indices: HashMap<i32, VecDeque<usize>>,
+    candidate_indices: Vec<HashMap<i32, usize>>, // store indices of tags of potential nested group
+    active_groups: Vec<FixGroup<V>>,             // contains the groups currently being parsed
+    group_defs: Vec<Option<&'d GroupDef>>,       // definitions of the active groups (if known)
+    dictionary: Option<&'d Dictionary>,
+    message_def: Option<&'d MessageDef>,
+    warnings: Vec<ParseWarning>,
+    checksum: Option<ChecksumCheck>,
+    body_length: Option<BodyLengthCheck>,
+    end_offset: usize, // offset of the end of the message in the input
+}
+
+impl<'a, 'd, V: From<&'a [u8]> + AsRef<[u8]>> Parser<'d, V> {
+    fn new() -> Self {
+        Self {
+            root_component: FixComponent::new(Vec::new()),
+            pending_tag_indices: HashMap::new(),
+            candidate_indices: vec![HashMap::new()],
+            active_groups: Vec::new(),
+            group_defs: Vec::new(),
+            dictionary: None,
+            message_def: None,
+            warnings: Vec::new(),
+            checksum: None,
+            body_length: None,
+            end_offset: 0,
+        }
+    }
+
+    pub(crate) fn parse(
+        input_message: &'a [u8],
+        options: &ParserOptions,
+    ) -> Result<ParsedMessage<V>, ParseError> {
+        Self::parse_with_dictionary(input_message, options, None)
+    }
+
+    // the groups defined in the dictionary for the type of the message are used to know where
+    // they start and end. The tags not found in the dictionary are parsed as usual
+    pub(crate) fn parse_with_dictionary(
+        input_message: &'a [u8],
+        options: &ParserOptions,
+        dictionary: Option<&'d Dictionary>,
+    ) -> Result<ParsedMessage<V>, ParseError> {
+        let mut parser = Parser::new();
+        let tag_values = parser.pre_process_message(input_message, options)?;
+
+        parser.message_def = dictionary.and_then(|dictionary| {
+            let msg_type = tag_values.iter().find(|tag_value| tag_value.tag == 35)?;
+            dictionary.message(std::str::from_utf8(msg_type.value).ok()?)
+        });
+        if parser.message_def.is_some() {
+            parser.dictionary = dictionary;
+        }
+
+        for (index, tag_value) in tag_values.iter().enumerate() {
+            parser
+                .pending_tag_indices
+                .entry(tag_value.tag)
+                .or_default()
+                .push_back(index);
+        }
+        parser.check_message_is_valid();
+
+        for (index, tag_value) in tag_values.iter().enumerate() {
+            parser.add_tag_value(tag_value, index);
+        }
+
+        if let Some(warning) = parser
+            .warnings
+            .iter()
+            .filter(|warning| options.rejects(&warning.kind))
+            .min_by_key(|warning| warning.offset)
+        {
+            return Err(rejection(warning));
+        }
+
+        Ok(ParsedMessage {
+            root_component: parser.root_component,
+            warnings: parser.warnings,
+            checksum: parser.checksum,
+            body_length: parser.body_length,
+        })
+    }
+
+    // from tag value encoding to a list of TagValue's
+    fn pre_process_message(
+        &mut self,
+        input_message: &'a [u8],
+        options: &ParserOptions,
+    ) -> Result<Vec<TagValue<'a>>, ParseError> {
+        const SHORTEST_MESSAGE_LENGTH: usize = 12; // len(8=FIX.N.M|X=) -> invalid still parsable
+
+        // trim input
+        let start = begin_string_start(input_message)
+            .ok_or(ParseError::MissingBeginString { offset: 0 })?;
+        let input_message = &input_message[start..];
+        if input_message.len() < SHORTEST_MESSAGE_LENGTH {
+            return Err(ParseError::TooShort {
+                offset: start,
+                length: input_message.len(),
+            });
+        }
+        let separator = get_separator(input_message, start, &options.separators)?;
+
+        let mut tag_values = Vec::new();
+        let mut fields = FieldsSummary::default();
+        let mut checksum_fields = None; // fields before tag 10
+        let mut body_start = None; // length of the fields up to tag 9
+        let mut pending_data_field = None; // (data tag, length) expected in the next field
+        let mut end_of_message_found = false;
+        let mut position = 0;
+        while position < input_message.len() {
+            let rest = &input_message[position..];
+            // offset of the token in the original input
+            let offset = start + position;
+            let token_length = pending_data_field
+                .take()
+                .and_then(|(data_tag, length)| {
+                    self.get_data_token_length(rest, data_tag, length, &separator, offset)
+                })
+                .or_else(|| find_bytes(rest, &separator))
+                .unwrap_or(rest.len());
+            let token = &rest[..token_length];
+            let previous_fields = fields;
+            fields.add(token, rest[token_length..].starts_with(&separator));
+            position += token_length + separator.len();
+
+            if token.is_empty() {
+                continue;
+            }
+            let equal_index = match token.iter().position(|byte| *byte == b'=') {
+                Some(equal_index) => equal_index,
+                None => {
+                    self.warn(WarningKind::IgnoredToken, None, offset, token);
+                    continue;
+                }
+            };
+            if end_of_message_found {
+                self.warn(WarningKind::TagAfterChecksum, None, offset, token);
+                break;
+            }
+
+            let tag = parse_bytes(&token[..equal_index]).unwrap_or(0);
+            let mut value = &token[equal_index + 1..];
+            if tag == 0 {
+                self.warn(WarningKind::InvalidTag, None, offset, token);
+                continue;
+            }
+            if value.is_empty() {
+                self.warn(WarningKind::EmptyValue, Some(tag), offset, token);
+            }
+            if tag == 9 && body_start.is_none() {
+                body_start = Some(fields.length);
+            }
+            if tag == 10 {
+                checksum_fields = Some(previous_fields);
+                if value.len() > 3 {
+                    let extra_offset = offset + equal_index + 4;
+                    self.warn(
+                        WarningKind::CharactersAfterChecksum,
+                        Some(tag),
+                        extra_offset,
+                        &value[3..],
+                    );
+                    value = &value[..3];
+                }
+            }
+            if let Some(data_tag) = options.data_fields.data_tag(tag) {
+                pending_data_field = parse_bytes(value).map(|length| (data_tag, length));
+            }
+            end_of_message_found = tag == 10;
+            tag_values.push(TagValue {
+                tag,
+                value,
+                offset,
+                token,
+            });
+        }
+        self.end_offset = start + input_message.len();
+        if options.require_standard_header {
+            self.check_standard_header(&tag_values);
+        }
+
+        let checksum_field = tag_values.last().filter(|tag_value| tag_value.tag == 10);
+        if options.verify_checksum {
+            if let (Some(checksum_fields), Some(checksum_field)) = (checksum_fields, checksum_field)
+            {
+                self.verify_checksum(checksum_fields.checksum(), checksum_field);
+            }
+        }
+        if options.verify_body_length {
+            let body_length_field = tag_values.iter().find(|tag_value| tag_value.tag == 9);
+            if let (Some(body_start), Some(body_length_field)) = (body_start, body_length_field) {
+                // from the end of tag 9 to the beginning of tag 10 (or the end of the message)
+                let body_end = checksum_fields.unwrap_or(fields).length;
+                self.verify_body_length(body_end - body_start, body_length_field);
+            }
+        }
+        Ok(tag_values)
+    }
+
+    // length of a data field whose value has exactly the length given in the previous field
+    fn get_data_token_length(
+        &mut self,
+        rest: &[u8],
+        data_tag: i32,
+        length: usize,
+        separator: &[u8],
+        offset: usize,
+    ) -> Option<usize> {
+        let tag_prefix = format!("{}=", data_tag);
+        if !rest.starts_with(tag_prefix.as_bytes()) {
+            return None;
+        }
+        let token_length = tag_prefix
+            .len()
+            .checked_add(length)
+            .filter(|token_length| *token_length <= rest.len());
+        let complete_value = token_length.filter(|token_length| {
+            *token_length == rest.len() || rest[*token_length..].starts_with(separator)
+        });
+        if complete_value.is_none() {
+            let token = &rest[..find_bytes(rest, separator).unwrap_or(rest.len())];
+            self.warn(
+                WarningKind::InvalidDataLength { length },
+                Some(data_tag),
+                offset,
+                token,
+            );
+            return None;
+        }
+        complete_value
+    }
+
+    fn check_message_is_valid(&mut self) {
+        if !self.pending_tag_indices.contains_key(&10) {
+            self.warn(WarningKind::MissingChecksum, Some(10), self.end_offset, b"");
+        }
+    }
+
+    fn check_standard_header(&mut self, tag_values: &[TagValue<'a>]) {
+        for (index, expected) in [8, 9, 35].iter().enumerate() {
+            let tag_value = tag_values.get(index);
+            if tag_value.is_some_and(|tag_value| tag_value.tag == *expected) {
+                continue;
+            }
+            let kind = WarningKind::MisplacedHeaderTag {
+                expected: *expected,
+            };
+            match tag_value {
+                Some(tag_value) => {
+                    self.warn(kind, Some(tag_value.tag), tag_value.offset, tag_value.token)
+                }
+                None => self.warn(kind, None, self.end_offset, b""),
+            }
+            return;
+        }
+    }
+
+    fn verify_checksum(&mut self, computed: u8, checksum_field: &TagValue<'a>) {
+        let checksum = ChecksumCheck {
+            declared: String::from_utf8_lossy(checksum_field.value).into_owned(),
+            computed,
+        };
+        if !checksum.is_valid() {
+            let kind = WarningKind::ChecksumMismatch {
+                declared: checksum.declared.clone(),
+                computed: verification::format_checksum(checksum.computed),
+            };
+            self.warn(kind, Some(10), checksum_field.offset, checksum_field.token);
+        }
+        self.checksum = Some(checksum);
+    }
+
+    fn verify_body_length(&mut self, computed: usize, body_length_field: &TagValue<'a>) {
+        let body_length = BodyLengthCheck {
+            declared: String::from_utf8_lossy(body_length_field.value).into_owned(),
+            computed,
+        };
+        if !body_length.is_valid() {
+            let kind = WarningKind::BodyLengthMismatch {
+                declared: body_length.declared.clone(),
+                computed: body_length.computed,
+            };
+            self.warn(
+                kind,
+                Some(9),
+                body_length_field.offset,
+                body_length_field.token,
+            );
+        }
+        self.body_length = Some(body_length);
+    }
+
+    fn warn(&mut self, kind: WarningKind, tag: Option<i32>, offset: usize, token: &[u8]) {
+        self.warnings
+            .push(ParseWarning::new(kind, tag, offset, token));
+    }
+
+    #[allow(unused_variables)]
+    fn add_tag_value(&mut self, tag_value: &TagValue<'a>, index: usize) {
+        let tag = tag_value.tag;
+        let offset = tag_value.offset;
+        debug!(
+            "{}Index {} - Add {} - {}",
+            self.get_spaces(),
+            index,
+            tag,
+            String::from_utf8_lossy(tag_value.value)
+        );
+        self.remove_pending_tag(tag);
+
+        if tag == 10 {
+            self.check_groups_are_complete(tag_value);
+        }
+
+        // the first instance of a group defined in the dictionary is opened by its delimiter
+        let defined_group = self.defined_group_started_by(tag);
+        if let Some(group_def) = defined_group {
+            let index_first_delimiter = self.get_entities().len();
+            if let Some((no_tag, repetitions)) = self.group_count(index_first_delimiter, tag_value)
+            {
+                self.open_group(
+                    tag,
+                    no_tag,
+                    repetitions,
+                    index_first_delimiter,
+                    Some(group_def),
+                );
+            }
+        } else {
+            while self.is_parsing_group() && !self.tag_in_group(tag) {
+                self.close_group();
+            }
+
+            // a repeated tag is a group whose NoXXX field is the one before its first occurrence
+            // (the instances it doesn't declare are reported as duplicates). If there is no field
+            // before it, the tag is kept as a duplicate
+            if self.repeated_candidate(tag) && !self.is_defined_tag(tag) {
+                let index_first_delimiter = self.get_index_of_candidate(tag);
+                if let Some((no_tag, repetitions)) =
+                    self.group_count(index_first_delimiter, tag_value)
+                {
+                    self.open_group(tag, no_tag, repetitions, index_first_delimiter, None);
+                }
+            }
+        }
+
+        if self.is_parsing_group() {
+            self.set_known_tag_in_group(tag);
+        }
+
+        if defined_group.is_none() && self.is_new_iteration(tag) {
+            self.create_new_group_instance();
+        } else {
+            self.register_candidate(tag);
+        }
+
+        if self.is_duplicate(tag) {
+            self.warn(
+                WarningKind::DuplicateTag,
+                Some(tag),
+                offset,
+                tag_value.token,
+            );
+        }
+        self.get_entities()
+            .push(FixEntity::Field(tag, V::from(tag_value.value)));
+    }
+
+    // the tag is already in the component being parsed, or it is the delimiter of an instance
+    // which the NoXXX field doesn't declare (eg: a repeated tag parsed as a group)
+    fn is_duplicate(&mut self, tag: i32) -> bool {
+        if self.get_entities().is_empty() && self.is_new_iteration(tag) {
+            return self.active_group().current_iteration > self.active_group().repetitions;
+        }
+        self.get_entities().iter().any(
+            |entity| matches!(entity, FixEntity::Field(field_tag, _value) if *field_tag == tag),
+        )
+    }
+
+    fn check_groups_are_complete(&mut self, tag_value: &TagValue<'a>) {
+        let incomplete_groups: Vec<i32> = self
+            .active_groups
+            .iter()
+            .filter(|group| group.current_iteration < group.repetitions)
+            .map(|group| group.no_tag)
+            .collect();
+        for no_tag in incomplete_groups {
+            self.warn(
+                WarningKind::EndOfMessageInGroup,
+                Some(no_tag),
+                tag_value.offset,
+                tag_value.token,
+            );
+        }
+    }
+
+    fn open_group(
+        &mut self,
+        group_delimiter: i32,
+        no_tag: i32,
+        repetitions: i32,
+        index_first_delimiter: usize,
+        group_def: Option<&'d GroupDef>,
+    ) {
+        debug!("{}INFO: Group detected", self.get_spaces());
+        let group = FixGroup::new(
+            group_delimiter,
+            no_tag,
+            repetitions,
+            index_first_delimiter,
+            self.get_component(),
+        );
+        self.active_groups.push(group);
+        self.group_defs.push(group_def);
+        self.candidate_indices.push(HashMap::new());
+    }
+
+    // definition of the group whose NoXXX field was the last one added, if the given tag is its
+    // delimiter
+    fn defined_group_started_by(&mut self, tag: i32) -> Option<&'d GroupDef> {
+        let no_tag = match self.get_entities().last() {
+            Some(FixEntity::Field(no_tag, _value)) => *no_tag,
+            _ => return None,
+        };
+        self.defined_group(no_tag)
+            .filter(|group_def| group_def.delimiter == tag)
+    }
+
+    // definition of a group which can be found in the component being parsed
+    fn defined_group(&self, no_tag: i32) -> Option<&'d GroupDef> {
+        let dictionary = self.dictionary?;
+        match self.group_defs.last() {
+            Some(group_def) => group_def.and_then(|group_def| group_def.group(no_tag)),
+            None => dictionary
+                .header()
+                .group(no_tag)
+                .or_else(|| self.message_def?.group(no_tag))
+                .or_else(|| dictionary.trailer().group(no_tag)),
+        }
+    }
+
+    fn is_defined_tag(&self, tag: i32) -> bool {
+        self.dictionary
+            .is_some_and(|dictionary| dictionary.field(tag).is_some())
+    }
+
+    fn get_candidates(&self) -> &HashMap<i32, usize> {
+        self.candidate_indices.last().unwrap()
+    }
+
+    fn get_candidates_mut(&mut self) -> &mut HashMap<i32, usize> {
+        self.candidate_indices.last_mut().unwrap()
+    }
+
+    fn get_index_of_candidate(&self, tag: i32) -> usize {
+        *self.get_candidates().get(&tag).unwrap()
+    }
+
+    // must be called before new insertion
+    fn register_candidate(&mut self, tag: i32) {
+        let candidate_index = self.get_entities().len();
+        self.get_candidates_mut().insert(tag, candidate_index);
+    }
+
+    // NoXXX tag and number of repetitions of a group (its NoXXX field is the one before its first
+    // delimiter). If the number is not valid, it is reported and the group has no repetitions
+    fn group_count(
+        &mut self,
+        index_first_delimiter: usize,
+        tag_value: &TagValue<'a>,
+    ) -> Option<(i32, i32)> {
+        let previous_entity = index_first_delimiter
+            .checked_sub(1)
+            .and_then(|index| self.get_entities().get(index));
+        let (no_tag, value) = match previous_entity {
+            Some(FixEntity::Field(no_tag, value)) => (*no_tag, value.as_ref()),
+            _ => {
+                self.warn(
+                    WarningKind::MissingGroupCount,
+                    Some(tag_value.tag),
+                    tag_value.offset,
+                    tag_value.token,
+                );
+                return None;
+            }
+        };
+        match parse_bytes(value).filter(|repetitions| *repetitions >= 0) {
+            Some(repetitions) => Some((no_tag, repetitions)),
+            None => {
+                let kind = WarningKind::InvalidGroupCount {
+                    value: String::from_utf8_lossy(value).into_owned(),
+                };
+                self.warn(kind, Some(no_tag), tag_value.offset, tag_value.token);
+                Some((no_tag, 0))
+            }
+        }
+    }
+
+    fn repeated_candidate(&mut self, tag: i32) -> bool {
+        self.get_candidates().contains_key(&tag)
+    }
+
+    fn get_next_index_of_pending_tag(&self, tag: i32) -> Option<&usize> {
+        self.pending_tag_indices.get(&tag).unwrap().front()
+    }
+
+    fn remove_pending_tag(&mut self, tag: i32) {
+        self.pending_tag_indices.get_mut(&tag).unwrap().pop_front();
+    }
+
+    fn close_group(&mut self) {
+        debug!("{}INFO: Stop parsing group\n", self.get_spaces());
+        let closed_group = self.active_groups.pop().unwrap();
+        self.get_component()
+            .entities
+            .push(FixEntity::Group(closed_group));
+        self.group_defs.pop();
+        self.candidate_indices.pop();
+    }
+
+    fn is_new_iteration(&self, tag: i32) -> bool {
+        self.is_parsing_group() && tag == self.active_group().delimiter
+    }
+
+    fn increment_iteration(&mut self) {
+        debug!(
+            "{}-- repetition {} --",
+            self.get_spaces(),
+            self.active_group().current_iteration + 1
+        );
+        self.active_group_mut().current_iteration += 1
+    }
+
+    fn get_entities(&mut self) -> &mut Vec<FixEntity<V>> {
+        &mut self.get_component().entities
+    }
+
+    fn create_new_group_instance(&mut self) {
+        self.get_candidates_mut().clear();
+        self.increment_iteration();
+        self.active_group_mut().create_new_instance();
+    }
+
+    #[allow(dead_code)]
+    fn get_spaces(&self) -> String {
+        " ".repeat(self.active_groups.len() * 2)
+    }
+
+    fn set_known_tag_in_group(&mut self, tag: i32) {
+        self.active_group_mut().insert_known_tag(tag);
+    }
+
+    fn get_component(&mut self) -> &mut FixComponent<V> {
+        if self.is_parsing_group() {
+            self.active_group_mut().instances.last_mut().unwrap()
+        } else {
+            &mut self.root_component
+        }
+    }
+
+    fn is_parsing_group(&self) -> bool {
+        !self.active_groups.is_empty()
+    }
+
+    fn active_group(&self) -> &FixGroup<V> {
+        self.active_groups.last().unwrap()
+    }
+
+    fn active_group_mut(&mut self) -> &mut FixGroup<V> {
+        self.active_groups.last_mut().unwrap()
+    }
+
+    fn tag_in_group(&mut self, tag: i32) -> bool {
+        if tag == 10 {
+            return false;
+        }
+        if let Some(Some(group_def)) = self.group_defs.last() {
+            if group_def.has_member_tag(tag) {
+                return true;
+            }
+            if self.is_defined_tag(tag) {
+                return false;
+            }
+        }
+        // from cheaper to more expensive check
+        !self.is_last_iteration()
+            || self.is_known_group_tag(tag)
+            || self.pending_tag_in_last_instance()
+    }
+
+    fn pending_tag_in_last_instance(&mut self) -> bool {
+        self.active_group().known_tags.iter().any(|known_tag| {
+            if let Some(tag_index) = self.get_next_index_of_pending_tag(*known_tag) {
+                return self.index_belongs_to_current_group(*tag_index);
+            }
+            false
+        })
+    }
+
+    fn index_belongs_to_current_group(&self, tag_index: usize) -> bool {
+        if let Some(delimiter_index) =
+            self.get_next_index_of_pending_tag(self.active_group().delimiter)
+        {
+            return tag_index < *delimiter_index;
+        }
+        true
+    }
+
+    fn is_known_group_tag(&self, tag: i32) -> bool {
+        self.active_group().known_tags.contains(&tag)
+    }
+
+    fn is_last_iteration(&self) -> bool {
+        self.active_group().current_iteration == self.active_group().repetitions
+    }
+}
+
+// separators which contain alphanumeric characters, so they are not detected as the bytes before
+// the second tag
+const SPELLED_SEPARATORS: [&[u8]; 2] = [b"^A", b"\\x01"];
+
+// get FIX values separator (eg: 0x01, |, ^A) including the whitespace around it (eg: " | "). If no
+// separators are given, it is detected from the bytes between the BeginString and the second tag
+pub(crate) fn get_separator(
+    fix_msg: &[u8],
+    offset: usize,
+    separators: &[Vec<u8>],
+) -> Result<Vec<u8>, ParseError> {
+    const VALUE_START: usize = 5; // len(8=FIX)
+    if !is_fix_version(&fix_msg[2..]) {
+        return Err(ParseError::InvalidBeginString { offset });
+    }
+    // the separator is before the equal sign of the second field
+    let first_field_end = fix_msg[VALUE_START..]
+        .iter()
+        .position(|byte| *byte == b'=')
+        .map_or(fix_msg.len(), |index| VALUE_START + index);
+    let first_field = &fix_msg[..first_field_end];
+
+    let candidates: Vec<&[u8]> = if separators.is_empty() {
+        SPELLED_SEPARATORS.to_vec()
+    } else {
+        separators.iter().map(Vec::as_slice).collect()
+    };
+    let found = candidates
+        .iter()
+        .filter(|candidate| !candidate.is_empty())
+        .filter_map(|candidate| {
+            find_bytes(&first_field[VALUE_START..], candidate)
+                .map(|index| (VALUE_START + index, candidate.len()))
+        })
+        .min();
+
+    let (separator_start, separator_end) = match found {
+        Some((index, length)) => (index, index + length),
+        None if separators.is_empty() => {
+            // the non-alphanumeric bytes before the digits of the second tag
+            let tag_start = first_field.len()
+                - first_field
+                    .iter()
+                    .rev()
+                    .take_while(|byte| byte.is_ascii_digit())
+                    .count();
+            let separator_length = first_field[VALUE_START..tag_start]
+                .iter()
+                .rev()
+                .take_while(|byte| !byte.is_ascii_alphanumeric() && **byte != b'.')
+                .count();
+            (tag_start - separator_length, tag_start)
+        }
+        None => (first_field.len(), first_field.len()),
+    };
+    if separator_start == separator_end {
+        return Err(ParseError::MissingSeparator {
+            offset: offset + separator_start,
+        });
+    }
+
+    // whitespace around the separator is part of it (eg: 8=FIX.4.4 | 9=5)
+    let is_whitespace = |byte: &&u8| **byte == b' ' || **byte == b'\t';
+    let separator_start = separator_start
+        - fix_msg[VALUE_START..separator_start]
+            .iter()
+            .rev()
+            .take_while(is_whitespace)
+            .count();
+    let separator_end = separator_end
+        + fix_msg[separator_end..]
+            .iter()
+            .take_while(is_whitespace)
+            .count();
+    Ok(fix_msg[separator_start..separator_end].to_vec())
+}
+
+// whether the value begins with a FIX version (eg: FIX.4.4, FIXT.1.1, FIX.5.0SP2, FIX.4.2-VENUE)
+fn is_fix_version(value: &[u8]) -> bool {
+    let version = value
+        .strip_prefix(b"FIXT")
+        .or_else(|| value.strip_prefix(b"FIX"));
+    matches!(version, Some([_, major, _, minor, ..]) if major.is_ascii_digit() && minor.is_ascii_digit())
+}
+
+// error returned when the options reject the given warning (the ones about the NoXXX field of a
+// group have their own errors)
+fn rejection(warning: &ParseWarning) -> ParseError {
+    match &warning.kind {
+        WarningKind::MissingGroupCount => ParseError::MissingGroupCount {
+            tag: warning.tag.unwrap_or_default(),
+            offset: warning.offset,
+        },
+        WarningKind::InvalidGroupCount { value } => ParseError::InvalidGroupCount {
+            tag: warning.tag.unwrap_or_default(),
+            value: value.clone(),
+            offset: warning.offset,
+        },
+        _ => ParseError::Rejected {
+            warning: warning.clone(),
+        },
+    }
+}
+
+pub(crate) const BEGIN_STRING: &[u8] = b"8=FIX";
+
+// where the message begins (anything before it is ignored). If there is no BeginString with a FIX
+// version, it is the first tag 8 (so that its value is reported as invalid)
+pub(crate) fn begin_string_start(input_message: &[u8]) -> Option<usize> {
+    find_begin_string(input_message).or_else(|| find_tag_start(input_message, b"8="))
+}
+
+// index of the next "8=FIX" which is not the end of another field (eg: 58=FIX)
+pub(crate) fn find_begin_string(bytes: &[u8]) -> Option<usize> {
+    find_tag_start(bytes, BEGIN_STRING)
+}
+
+fn find_tag_start(bytes: &[u8], tag_start: &[u8]) -> Option<usize> {
+    let mut from = 0;
+    while let Some(index) = find_bytes(&bytes[from..], tag_start) {
+        let index = from + index;
+        if index == 0 || !bytes[index - 1].is_ascii_digit() {
+            return Some(index);
+        }
+        from = index + 1;
+    }
+    None
+}
+
+pub(crate) fn find_bytes(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .position(|window| window == needle)
+}
+
+// parse ascii values (eg: tags, lengths, number of repetitions)
+pub(crate) fn parse_bytes<T: std::str::FromStr>(bytes: &[u8]) -> Option<T> {
+    std::str::from_utf8(bytes).ok()?.parse().ok()
+}
@@ -0,0 +1,186 @@
+use crate::model::{FixComponent, FixEntity};
+use crate::PathError;
+use std::fmt;
+use std::str::FromStr;
+
+/// A path to select values from a message, going through its repeating groups.
+///
+/// A path is a list of tags separated by `.`. All the tags but the last one are NoXXX tags of
+/// groups, and they can be followed by a selector of instances:
+///
+/// - `[N]`: the instance at index N (starting at 0)
+/// - `[*]`: all the instances (the same as not using a selector)
+/// - `[?TAG=VALUE]`: the instances which have a field TAG whose value is VALUE
+///
+/// The last tag is the tag of the fields whose values are selected.
+///
+/// # Example
+///
+/// ```rust
+/// use fixparser::{FixMessage, FixPath};
+///
+/// let input = "8=FIX.4.4 | 555=2 | 600=CGY | 604=2 | 605=F7 | 605=CGYU0 | 600=CGZ | 10=209";
+/// let fix_message = FixMessage::from_tag_value(input).unwrap();
+/// let path: FixPath = "555[0].604[1].605".parse().unwrap();
+/// assert_eq!(vec!["CGYU0"], fix_message.query(&path));
+/// assert_eq!(vec!["CGY", "CGZ"], fix_message.query(&"555[*].600".parse().unwrap()));
+/// assert_eq!(vec!["F7", "CGYU0"], fix_message.query(&"555[?600=CGY].604.605".parse().unwrap()));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FixPath {
+    groups: Vec<(i32, Selector)>,
+    tag: i32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Selector {
+    All,
+    Index(usize),
+    Filter(i32, String),
+}
+
+impl FixPath {
+    // path to a field given the NoXXX tag and the index of each instance which contains it
+    pub(crate) fn from_indexes(groups: &[(i32, usize)], tag: i32) -> Self {
+        Self {
+            groups: groups
+                .iter()
+                .map(|(no_tag, index)| (*no_tag, Selector::Index(*index)))
+                .collect(),
+            tag,
+        }
+    }
+
+    pub(crate) fn evaluate<'m, V: AsRef<[u8]>>(&self, entities: &'m [FixEntity<V>]) -> Vec<&'m V> {
+        let mut components = vec![entities];
+        for (no_tag, selector) in &self.groups {
+            components = components
+                .into_iter()
+                .flatten()
+                .filter_map(|entity| match entity {
+                    FixEntity::Group(group) if group.no_tag == *no_tag => Some(group),
+                    _ => None,
+                })
+                .flat_map(|group| select_instances(&group.instances, selector))
+                .map(|instance| &instance.entities[..])
+                .collect();
+        }
+
+        components
+            .into_iter()
+            .flatten()
+            .filter_map(|entity| match entity {
+                FixEntity::Field(tag, value) if *tag == self.tag => Some(value),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+fn select_instances<'m, V: AsRef<[u8]>>(
+    instances: &'m [FixComponent<V>],
+    selector: &Selector,
+) -> Vec<&'m FixComponent<V>> {
+    match selector {
+        Selector::All => instances.iter().collect(),
+        Selector::Index(index) => instances.get(*index).into_iter().collect(),
+        Selector::Filter(tag, value) => instances
+            .iter()
+            .filter(|instance| {
+                instance.get_field(*tag).map(AsRef::as_ref) == Some(value.as_bytes())
+            })
+            .collect(),
+    }
+}
+
+impl FromStr for FixPath {
+    type Err = PathError;
+
+    fn from_str(path: &str) -> Result<Self, Self::Err> {
+        let mut steps = Vec::new();
+        let mut offset = 0;
+        loop {
+            let (tag, tag_length) = parse_tag(&path[offset..], offset)?;
+            offset += tag_length;
+
+            let selector_offset = offset;
+            let selector = if path[offset..].starts_with('[') {
+                let length = path[offset..]
+                    .find(']')
+                    .ok_or(PathError::InvalidSelector { offset })?;
+                let selector = parse_selector(&path[offset + 1..offset + length], offset)?;
+                offset += length + 1;
+                Some(selector)
+            } else {
+                None
+            };
+            steps.push((tag, selector, selector_offset));
+
+            match path[offset..].chars().next() {
+                None => break,
+                Some('.') => offset += 1,
+                Some(_) => return Err(PathError::UnexpectedCharacter { offset }),
+            }
+        }
+
+        let (tag, selector, offset) = steps.pop().expect("There is at least one step");
+        if selector.is_some() {
+            return Err(PathError::SelectorOnField { offset });
+        }
+        Ok(Self {
+            groups: steps
+                .into_iter()
+                .map(|(no_tag, selector, _selector_offset)| {
+                    (no_tag, selector.unwrap_or(Selector::All))
+                })
+                .collect(),
+            tag,
+        })
+    }
+}
+
+// returns the tag and the number of bytes it takes
+fn parse_tag(path: &str, offset: usize) -> Result<(i32, usize), PathError> {
+    let length = path
+        .find(|character: char| !character.is_ascii_digit())
+        .unwrap_or(path.len());
+    path[..length]
+        .parse()
+        .map(|tag| (tag, length))
+        .map_err(|_| PathError::InvalidTag { offset })
+}
+
+// parses the content between the brackets which start at the given offset
+fn parse_selector(selector: &str, offset: usize) -> Result<Selector, PathError> {
+    let invalid_selector = PathError::InvalidSelector { offset };
+    if selector == "*" {
+        return Ok(Selector::All);
+    }
+    if let Some(filter) = selector.strip_prefix('?') {
+        let (tag, tag_length) = parse_tag(filter, offset + 2)?;
+        return match filter[tag_length..].strip_prefix('=') {
+            Some(value) => Ok(Selector::Filter(tag, value.to_string())),
+            None => Err(invalid_selector),
+        };
+    }
+    if selector.is_empty() || !selector.bytes().all(|byte| byte.is_ascii_digit()) {
+        return Err(invalid_selector);
+    }
+    selector
+        .parse()
+        .map(Selector::Index)
+        .map_err(|_| invalid_selector)
+}
+
+impl fmt::Display for FixPath {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (no_tag, selector) in &self.groups {
+            match selector {
+                Selector::All => write!(f, "{}[*].", no_tag)?,
+                Selector::Index(index) => write!(f, "{}[{}].", no_tag, index)?,
+                Selector::Filter(tag, value) => write!(f, "{}[?{}={}].", no_tag, tag, value)?,
+            }
+        }
+        write!(f, "{}", self.tag)
+    }
+}
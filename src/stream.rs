@@ -0,0 +1,246 @@
+use crate::parser::{find_begin_string, find_bytes, get_separator, parse_bytes, BEGIN_STRING};
+use crate::{FixMessage, ParseError, ParserOptions};
+use std::io::{ErrorKind, Read};
+
+const READ_SIZE: usize = 16 * 1024;
+const SEPARATOR_LOOKAHEAD: usize = 32; // bytes needed after the begin string to detect the separator
+
+/// Parses the messages found one after the other in a stream (eg: a file, a TCP capture or a
+/// buffer), yielding them in order.
+///
+/// Messages can be concatenated with or without anything between them (eg: new lines, log
+/// prefixes or any other garbage, which is skipped). A message ends after its CheckSum field
+/// (tag 10), or where the next message begins if it has no CheckSum. If the message has a
+/// BodyLength (tag 9) and a single-byte separator, the CheckSum is expected where BodyLength
+/// says, so data fields can contain anything.
+///
+/// The offsets of the errors and warnings of each message are relative to the beginning of the
+/// message (see [`FixStreamParser::offset`]).
+///
+/// # Example
+///
+/// ```rust
+/// let input = "garbage8=FIX.4.4|35=0|10=209|\n8=FIX.4.4|35=1|10=210|";
+/// let mut messages = fixparser::FixStreamParser::new(input.as_bytes());
+/// assert_eq!(Some("0"), messages.next().unwrap().unwrap().get(35));
+/// assert_eq!(7, messages.offset());
+/// assert_eq!(Some("1"), messages.next().unwrap().unwrap().get(35));
+/// assert_eq!(30, messages.offset());
+/// assert!(messages.next().is_none());
+/// ```
+///
+/// To parse a file: `FixStreamParser::new(std::fs::File::open("capture.bin")?)`.
+#[derive(Debug)]
+pub struct FixStreamParser<R> {
+    reader: R,
+    options: ParserOptions,
+    buffer: Vec<u8>,
+    position: usize, // beginning of the bytes of the buffer which were not parsed yet
+    end_of_stream: bool,
+    consumed: u64, // bytes of the stream before the position
+    offset: u64,   // offset of the last message in the stream
+}
+
+// where the next message is in the buffer
+enum Framing {
+    Message { start: usize, end: usize },
+    // more bytes are needed to find the message. The bytes before the given index can be dropped
+    Incomplete { garbage: usize },
+    EndOfStream,
+}
+
+impl<R: Read> FixStreamParser<R> {
+    /// Creates a parser which reads the messages from the given reader (eg: a `File`, a
+    /// `TcpStream`, a `BufReader` or a `&[u8]`).
+    pub fn new(reader: R) -> Self {
+        Self::with_options(reader, ParserOptions::default())
+    }
+
+    /// Same as [`FixStreamParser::new`], but each message is parsed with the given options.
+    pub fn with_options(reader: R, options: ParserOptions) -> Self {
+        Self {
+            reader,
+            options,
+            buffer: Vec::new(),
+            position: 0,
+            end_of_stream: false,
+            consumed: 0,
+            offset: 0,
+        }
+    }
+
+    /// Offset (relative to the beginning of the stream) of the last message returned.
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    /// Gets back the reader.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    // the indices are relative to the position
+    fn frame(&self) -> Framing {
+        let buffer = &self.buffer[self.position..];
+        let mut from = 0;
+        loop {
+            let start = match find_begin_string(&buffer[from..]) {
+                Some(start) => from + start,
+                None if self.end_of_stream => return Framing::EndOfStream,
+                None => {
+                    // the end of the buffer can be the beginning of the next message
+                    let garbage = buffer.len().saturating_sub(BEGIN_STRING.len());
+                    return Framing::Incomplete { garbage };
+                }
+            };
+            let message = &buffer[start..];
+            let incomplete = Framing::Incomplete { garbage: start };
+            if !self.end_of_stream && message.len() < SEPARATOR_LOOKAHEAD {
+                return incomplete;
+            }
+            let separator = match get_separator(message, 0, &self.options.separators) {
+                Ok(separator) => separator,
+                // it looked like a begin string, but it was not
+                Err(_) => {
+                    from = start + 1;
+                    continue;
+                }
+            };
+            return match message_length(message, &separator, self.end_of_stream) {
+                Some(length) => Framing::Message {
+                    start,
+                    end: start + length,
+                },
+                None => incomplete,
+            };
+        }
+    }
+
+    // reads more bytes at the end of the buffer, dropping the bytes already parsed
+    fn fill_buffer(&mut self) -> Result<(), ParseError> {
+        self.buffer.drain(..self.position);
+        self.position = 0;
+        let mut chunk = [0; READ_SIZE];
+        let read = loop {
+            match self.reader.read(&mut chunk) {
+                Err(error) if error.kind() == ErrorKind::Interrupted => continue,
+                read => break read,
+            }
+        };
+        match read {
+            Ok(read) => {
+                self.buffer.extend_from_slice(&chunk[..read]);
+                self.end_of_stream = read == 0;
+                Ok(())
+            }
+            Err(error) => {
+                // the messages already read are parsed anyway
+                self.end_of_stream = true;
+                Err(ParseError::Io {
+                    message: error.to_string(),
+                    offset: (self.consumed + self.buffer.len() as u64) as usize,
+                })
+            }
+        }
+    }
+
+    fn consume(&mut self, length: usize) {
+        self.position += length;
+        self.consumed += length as u64;
+    }
+}
+
+impl<R: Read> Iterator for FixStreamParser<R> {
+    type Item = Result<FixMessage, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.frame() {
+                Framing::Message { start, end } => {
+                    self.offset = self.consumed + start as u64;
+                    let message = &self.buffer[self.position + start..self.position + end];
+                    let message = FixMessage::from_bytes_with(message, &self.options);
+                    self.consume(end);
+                    return Some(message);
+                }
+                Framing::Incomplete { garbage } => {
+                    self.consume(garbage);
+                    if let Err(error) = self.fill_buffer() {
+                        return Some(Err(error));
+                    }
+                }
+                Framing::EndOfStream => {
+                    self.consume(self.buffer.len() - self.position);
+                    return None;
+                }
+            }
+        }
+    }
+}
+
+// length of the message which begins the given bytes (None if more bytes are needed to know it)
+fn message_length(message: &[u8], separator: &[u8], end_of_stream: bool) -> Option<usize> {
+    let checksum_tag = [separator, b"10="].concat();
+    // the next message is only looked for where this one could end
+    let next_message = |from: usize, to: usize| {
+        find_begin_string(&message[from.min(to)..to]).map(|index| from + index)
+    };
+
+    let mut checksum_start = None;
+    if let Some(index) = checksum_by_body_length(message, separator) {
+        let incomplete = index
+            .checked_add(checksum_tag.len())
+            .is_none_or(|end| end > message.len());
+        if incomplete && !end_of_stream && next_message(1, message.len()).is_none() {
+            return None;
+        }
+        if message
+            .get(index..)
+            .is_some_and(|rest| rest.starts_with(&checksum_tag))
+        {
+            checksum_start = Some(index);
+        }
+    }
+    let checksum_start = checksum_start.or_else(|| {
+        find_bytes(&message[BEGIN_STRING.len()..], &checksum_tag)
+            .map(|index| index + BEGIN_STRING.len())
+            .filter(|index| next_message(1, *index).is_none())
+    });
+
+    let end = match checksum_start {
+        // the next message can begin in the value of the checksum (eg: without a separator)
+        Some(index) => {
+            let value_start = index + checksum_tag.len();
+            match find_bytes(&message[value_start..], separator) {
+                Some(length) => {
+                    let value_end = value_start + length + separator.len();
+                    next_message(value_start + 1, value_end).or(Some(value_end))
+                }
+                None => next_message(value_start + 1, message.len()),
+            }
+        }
+        // a message without checksum ends where the next one begins
+        None => next_message(1, message.len()),
+    };
+    match end {
+        Some(end) => Some(end),
+        None if end_of_stream => Some(message.len()),
+        None => None,
+    }
+}
+
+// index of the separator before tag 10 according to the BodyLength of the message (only if the
+// separator has a single byte, as BodyLength counts SOH as a single byte)
+fn checksum_by_body_length(message: &[u8], separator: &[u8]) -> Option<usize> {
+    if separator.len() != 1 {
+        return None;
+    }
+    let body_length_start = find_bytes(message, separator)? + 1;
+    let body_length_field = &message[body_length_start..];
+    let body_length_end = find_bytes(body_length_field, separator)?;
+    let body_length: usize =
+        parse_bytes(body_length_field[..body_length_end].strip_prefix(b"9=")?)?;
+    let body_start = body_length_start + body_length_end + 1;
+    // a BodyLength too big to be real is ignored, so tag 10 is looked for
+    body_start.checked_add(body_length)?.checked_sub(1)
+}
@@ -0,0 +1,94 @@
+use crate::model::{FixComponent, FixEntity};
+use crate::verification::{self, FieldsSummary};
+
+pub(crate) const SOH: &[u8] = b"\x01";
+
+// encodes the message in tag=value format, recomputing BodyLength (9) and CheckSum (10) as if SOH
+// was the separator. The count of the groups is the number of their instances
+pub(crate) fn encode<V: AsRef<[u8]>>(
+    root_component: &FixComponent<V>,
+    separator: &[u8],
+) -> Vec<u8> {
+    // without BeginString it is not a complete message, so the fields are encoded as they are
+    let fields = if root_component.get_field(8).is_some() {
+        encode_fields(root_component).fields
+    } else {
+        let mut fields = Vec::new();
+        for entity in &root_component.entities {
+            add_entity(entity, &mut fields);
+        }
+        fields
+    };
+    let mut message = Vec::new();
+    for field in fields {
+        message.extend_from_slice(&field);
+        message.extend_from_slice(separator);
+    }
+    message
+}
+
+// values of BodyLength (9) and CheckSum (10) of the message once encoded
+pub(crate) fn body_length_and_checksum<V: AsRef<[u8]>>(
+    root_component: &FixComponent<V>,
+) -> (usize, String) {
+    let encoded = encode_fields(root_component);
+    (encoded.body_length, encoded.checksum)
+}
+
+struct EncodedFields {
+    fields: Vec<Vec<u8>>, // tag=value of each field (including 9 and 10)
+    body_length: usize,
+    checksum: String,
+}
+
+fn encode_fields<V: AsRef<[u8]>>(root_component: &FixComponent<V>) -> EncodedFields {
+    let mut body = Vec::new();
+    for entity in &root_component.entities {
+        match entity {
+            FixEntity::Field(8, _) | FixEntity::Field(9, _) | FixEntity::Field(10, _) => {}
+            entity => add_entity(entity, &mut body),
+        }
+    }
+
+    let mut fields = Vec::new();
+    if let Some(begin_string) = root_component.get_field(8) {
+        fields.push(field(8, begin_string.as_ref()));
+    }
+    let body_length: usize = body.iter().map(|field| field.len() + SOH.len()).sum();
+    fields.push(field(9, body_length.to_string().as_bytes()));
+    fields.extend(body);
+
+    let mut summary = FieldsSummary::default();
+    fields.iter().for_each(|field| summary.add(field, true));
+    let checksum = verification::format_checksum(summary.checksum());
+    fields.push(field(10, checksum.as_bytes()));
+
+    EncodedFields {
+        fields,
+        body_length,
+        checksum,
+    }
+}
+
+fn add_entity<V: AsRef<[u8]>>(entity: &FixEntity<V>, fields: &mut Vec<Vec<u8>>) {
+    match entity {
+        FixEntity::Field(tag, value) => fields.push(field(*tag, value.as_ref())),
+        // a group without instances is omitted, as its NoXXX field would be 0
+        FixEntity::Group(group) if group.instances.is_empty() => {}
+        FixEntity::Group(group) => {
+            let count = group.instances.len().to_string();
+            fields.push(field(group.no_tag, count.as_bytes()));
+            group
+                .instances
+                .iter()
+                .flat_map(|instance| instance.entities.iter())
+                .for_each(|entity| add_entity(entity, fields));
+        }
+    }
+}
+
+fn field(tag: i32, value: &[u8]) -> Vec<u8> {
+    let mut field = format!("{}=", tag).into_bytes();
+    field.extend_from_slice(value);
+    field
+}
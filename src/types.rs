@@ -0,0 +1,344 @@
+use std::fmt;
+use std::str::FromStr;
+
+/// Type of the value of a field, as expected by the typed getters (eg: [`FixMessage::get_int`](crate::FixMessage::get_int)).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValueType {
+    Int,
+    Decimal,
+    Char,
+    Bool,
+    UtcTimestamp,
+    LocalMktDate,
+    MonthYear,
+}
+
+impl fmt::Display for ValueType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            ValueType::Int => "int",
+            ValueType::Decimal => "decimal",
+            ValueType::Char => "char",
+            ValueType::Bool => "boolean (Y/N)",
+            ValueType::UtcTimestamp => "UTCTimestamp",
+            ValueType::LocalMktDate => "LocalMktDate",
+            ValueType::MonthYear => "MonthYear",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Reasons why a string can't be parsed as one of the FIX value types.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValueError {
+    /// The string does not have the format of the type.
+    Invalid,
+    /// The string has the format of the type, but it doesn't fit in it.
+    OutOfRange,
+}
+
+impl fmt::Display for ValueError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ValueError::Invalid => write!(f, "invalid value"),
+            ValueError::OutOfRange => write!(f, "value out of range"),
+        }
+    }
+}
+
+impl std::error::Error for ValueError {}
+
+/// A decimal number without loss of precision (eg: prices, quantities).
+///
+/// Its value is `mantissa * 10^-scale`. Trailing zeros are kept, so `1.50` and `1.5` are different.
+///
+/// # Example
+///
+/// ```rust
+/// let price: fixparser::Decimal = "-12.050".parse().unwrap();
+/// assert_eq!((-12050, 3), (price.mantissa(), price.scale()));
+/// assert_eq!("-12.050", price.to_string());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Decimal {
+    mantissa: i128,
+    scale: u32,
+}
+
+impl Decimal {
+    pub fn new(mantissa: i128, scale: u32) -> Self {
+        Self { mantissa, scale }
+    }
+
+    pub fn mantissa(&self) -> i128 {
+        self.mantissa
+    }
+
+    /// Number of digits after the decimal point.
+    pub fn scale(&self) -> u32 {
+        self.scale
+    }
+
+    /// Converts the number to the closest `f64` (precision may be lost).
+    pub fn to_f64(&self) -> f64 {
+        self.to_string().parse().unwrap_or(f64::NAN)
+    }
+}
+
+impl FromStr for Decimal {
+    type Err = ValueError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let unsigned_value = value.strip_prefix('-').unwrap_or(value);
+        let (integer, fraction) = match unsigned_value.find('.') {
+            Some(index) => (&unsigned_value[..index], &unsigned_value[index + 1..]),
+            None => (unsigned_value, ""),
+        };
+        let all_digits = |digits: &str| digits.bytes().all(|byte| byte.is_ascii_digit());
+        if integer.len() + fraction.len() == 0 || !all_digits(integer) || !all_digits(fraction) {
+            return Err(ValueError::Invalid);
+        }
+
+        let mut mantissa = format!("{}{}", integer, fraction)
+            .parse::<i128>()
+            .map_err(|_| ValueError::OutOfRange)?;
+        if unsigned_value.len() != value.len() {
+            mantissa = -mantissa;
+        }
+        Ok(Self::new(mantissa, fraction.len() as u32))
+    }
+}
+
+impl fmt::Display for Decimal {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let digits = format!(
+            "{:0width$}",
+            self.mantissa.unsigned_abs(),
+            width = self.scale as usize + 1
+        );
+        let (integer, fraction) = digits.split_at(digits.len() - self.scale as usize);
+        let sign = if self.mantissa < 0 { "-" } else { "" };
+        if fraction.is_empty() {
+            write!(f, "{}{}", sign, integer)
+        } else {
+            write!(f, "{}{}.{}", sign, integer, fraction)
+        }
+    }
+}
+
+/// A UTCTimestamp value (`YYYYMMDD-HH:MM:SS[.sss[sss[sss]]]`).
+///
+/// # Example
+///
+/// ```rust
+/// let timestamp: fixparser::UtcTimestamp = "20200520-19:15:45.099000".parse().unwrap();
+/// assert_eq!((19, 15, 45), (timestamp.hour, timestamp.minute, timestamp.second));
+/// assert_eq!((99_000_000, 6), (timestamp.nanosecond, timestamp.fraction_digits));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct UtcTimestamp {
+    pub year: u16,
+    pub month: u8,
+    pub day: u8,
+    pub hour: u8,
+    pub minute: u8,
+    pub second: u8, // 60 for leap seconds
+    pub nanosecond: u32,
+    /// Precision of the value: 0 (seconds), 3 (millis), 6 (micros) or 9 (nanos).
+    pub fraction_digits: u8,
+}
+
+impl FromStr for UtcTimestamp {
+    type Err = ValueError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        if value.len() < 17 || value.as_bytes()[8] != b'-' || !value.is_ascii() {
+            return Err(ValueError::Invalid);
+        }
+        let date = value[..8].parse::<LocalMktDate>()?;
+        let (time, fraction) = value[9..].split_at(8);
+        let time = time.as_bytes();
+        if time[2] != b':' || time[5] != b':' {
+            return Err(ValueError::Invalid);
+        }
+        let hour = parse_digits(&value[9..11])? as u8;
+        let minute = parse_digits(&value[12..14])? as u8;
+        let second = parse_digits(&value[15..17])? as u8;
+        if hour > 23 || minute > 59 || second > 60 {
+            return Err(ValueError::Invalid);
+        }
+
+        let fraction = match fraction.strip_prefix('.') {
+            Some(fraction) => fraction,
+            None if fraction.is_empty() => "",
+            None => return Err(ValueError::Invalid),
+        };
+        if ![0, 3, 6, 9].contains(&fraction.len()) || (fraction.is_empty() && value.len() > 17) {
+            return Err(ValueError::Invalid);
+        }
+        let nanosecond = match fraction.len() {
+            0 => 0,
+            digits => parse_digits(fraction)? * 10u32.pow(9 - digits as u32),
+        };
+
+        Ok(Self {
+            year: date.year,
+            month: date.month,
+            day: date.day,
+            hour,
+            minute,
+            second,
+            nanosecond,
+            fraction_digits: fraction.len() as u8,
+        })
+    }
+}
+
+impl fmt::Display for UtcTimestamp {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{:04}{:02}{:02}-{:02}:{:02}:{:02}",
+            self.year, self.month, self.day, self.hour, self.minute, self.second
+        )?;
+        if self.fraction_digits > 0 {
+            let fraction = self.nanosecond / 10u32.pow(9 - u32::from(self.fraction_digits));
+            write!(
+                f,
+                ".{:0width$}",
+                fraction,
+                width = self.fraction_digits as usize
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// A LocalMktDate value (`YYYYMMDD`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct LocalMktDate {
+    pub year: u16,
+    pub month: u8,
+    pub day: u8,
+}
+
+impl FromStr for LocalMktDate {
+    type Err = ValueError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        if value.len() != 8 || !value.is_ascii() {
+            return Err(ValueError::Invalid);
+        }
+        let year = parse_digits(&value[..4])? as u16;
+        let month = parse_digits(&value[4..6])? as u8;
+        let day = parse_digits(&value[6..8])? as u8;
+        if month == 0 || month > 12 || day == 0 || day > days_in_month(year, month) {
+            return Err(ValueError::Invalid);
+        }
+        Ok(Self { year, month, day })
+    }
+}
+
+impl fmt::Display for LocalMktDate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:04}{:02}{:02}", self.year, self.month, self.day)
+    }
+}
+
+/// A MonthYear value (`YYYYMM`, `YYYYMMDD` or `YYYYMMwN`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MonthYear {
+    pub year: u16,
+    pub month: u8,
+    pub day: Option<u8>,
+    /// Week of the month (1 to 5).
+    pub week: Option<u8>,
+}
+
+impl FromStr for MonthYear {
+    type Err = ValueError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        if !value.is_ascii() || value.len() < 6 {
+            return Err(ValueError::Invalid);
+        }
+        let year = parse_digits(&value[..4])? as u16;
+        let month = parse_digits(&value[4..6])? as u8;
+        if month == 0 || month > 12 {
+            return Err(ValueError::Invalid);
+        }
+        let (day, week) = match &value[6..] {
+            "" => (None, None),
+            week if week.len() == 2 && week.starts_with('w') => {
+                match parse_digits(&week[1..])? as u8 {
+                    week @ 1..=5 => (None, Some(week)),
+                    _ => return Err(ValueError::Invalid),
+                }
+            }
+            day if day.len() == 2 => match parse_digits(day)? as u8 {
+                day if day > 0 && day <= days_in_month(year, month) => (Some(day), None),
+                _ => return Err(ValueError::Invalid),
+            },
+            _ => return Err(ValueError::Invalid),
+        };
+        Ok(Self {
+            year,
+            month,
+            day,
+            week,
+        })
+    }
+}
+
+impl fmt::Display for MonthYear {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:04}{:02}", self.year, self.month)?;
+        if let Some(day) = self.day {
+            write!(f, "{:02}", day)?;
+        } else if let Some(week) = self.week {
+            write!(f, "w{}", week)?;
+        }
+        Ok(())
+    }
+}
+
+pub(crate) fn parse_int(value: &str) -> Result<i64, ValueError> {
+    let digits = value.strip_prefix('-').unwrap_or(value);
+    if digits.is_empty() || !digits.bytes().all(|byte| byte.is_ascii_digit()) {
+        return Err(ValueError::Invalid);
+    }
+    value.parse().map_err(|_| ValueError::OutOfRange)
+}
+
+pub(crate) fn parse_bool(value: &str) -> Result<bool, ValueError> {
+    match value {
+        "Y" => Ok(true),
+        "N" => Ok(false),
+        _ => Err(ValueError::Invalid),
+    }
+}
+
+pub(crate) fn parse_char(value: &str) -> Result<char, ValueError> {
+    let mut chars = value.chars();
+    match (chars.next(), chars.next()) {
+        (Some(char), None) => Ok(char),
+        _ => Err(ValueError::Invalid),
+    }
+}
+
+// parse only digits (ie: without sign)
+fn parse_digits(digits: &str) -> Result<u32, ValueError> {
+    if digits.is_empty() || !digits.bytes().all(|byte| byte.is_ascii_digit()) {
+        return Err(ValueError::Invalid);
+    }
+    digits.parse().map_err(|_| ValueError::OutOfRange)
+}
+
+fn days_in_month(year: u16, month: u8) -> u8 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
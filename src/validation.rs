@@ -0,0 +1,278 @@
+use crate::dictionary::{find_group, has_member_tag};
+use crate::model::{FixComponent, FixEntity};
+use crate::types::{self, Decimal, LocalMktDate, MonthYear, UtcTimestamp};
+use crate::{Dictionary, FixPath, GroupDef, Member, ValueType};
+use std::collections::HashSet;
+use std::fmt;
+
+/// Kind of problem found when a message is validated against a dictionary.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IssueKind {
+    /// The message has no MsgType (tag 35) or its type is not defined in the dictionary, so no
+    /// other check is made.
+    UnknownMsgType,
+    /// A required field or group is missing.
+    MissingRequiredField,
+    /// The tag is not defined for the message (or for the group where it was found).
+    UndefinedTag,
+    /// The value doesn't have the format of the type of the field.
+    InvalidValue { expected: ValueType },
+    /// The values of the field are enumerated, but the value is not one of them.
+    ValueNotInEnum,
+    /// The value of the NoXXX field doesn't match the number of instances of the group.
+    GroupCountMismatch { declared: i32, instances: usize },
+}
+
+/// A problem found when a message is validated against a dictionary (see
+/// [`FixMessage::validate`](crate::FixMessage::validate)).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationIssue {
+    pub kind: IssueKind,
+    /// Tag of the field. For `GroupCountMismatch`, it is the NoXXX tag of the group.
+    pub tag: i32,
+    /// Where the field is in the message (eg: `555[1].600`). For missing fields, it is where they
+    /// were expected.
+    pub path: FixPath,
+    /// Value of the field (invalid UTF-8 sequences are replaced with `U+FFFD`). It is empty for
+    /// missing fields.
+    pub value: String,
+}
+
+impl fmt::Display for ValidationIssue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.kind {
+            IssueKind::UnknownMsgType => {
+                write!(f, "Message type [{}] is not defined", self.value)
+            }
+            IssueKind::MissingRequiredField => {
+                write!(f, "Required tag {} is missing ({})", self.tag, self.path)
+            }
+            IssueKind::UndefinedTag => {
+                write!(f, "Tag {} is not defined here ({})", self.tag, self.path)
+            }
+            IssueKind::InvalidValue { expected } => write!(
+                f,
+                "Value [{}] of tag {} is not a valid {} ({})",
+                self.value, self.tag, expected, self.path
+            ),
+            IssueKind::ValueNotInEnum => write!(
+                f,
+                "Value [{}] of tag {} is not one of its enumerated values ({})",
+                self.value, self.tag, self.path
+            ),
+            IssueKind::GroupCountMismatch {
+                declared,
+                instances,
+            } => write!(
+                f,
+                "Group {} declares {} instances, but {} were found ({})",
+                self.tag, declared, instances, self.path
+            ),
+        }
+    }
+}
+
+pub(crate) fn validate<V: AsRef<[u8]>>(
+    root: &FixComponent<V>,
+    dictionary: &Dictionary,
+) -> Vec<ValidationIssue> {
+    let mut validator = Validator {
+        dictionary,
+        groups: Vec::new(),
+        issues: Vec::new(),
+    };
+    let msg_type = root
+        .get_field(35)
+        .map(|value| String::from_utf8_lossy(value.as_ref()).into_owned())
+        .unwrap_or_default();
+    match dictionary.message(&msg_type) {
+        Some(message) => {
+            let members = [
+                &dictionary.header().members[..],
+                &message.members,
+                &dictionary.trailer().members,
+            ];
+            validator.component(root, Some(&members));
+        }
+        None => validator.report(IssueKind::UnknownMsgType, 35, msg_type),
+    }
+    validator.issues
+}
+
+struct Validator<'d> {
+    dictionary: &'d Dictionary,
+    groups: Vec<(i32, usize)>, // NoXXX tag and index of the instances being validated
+    issues: Vec<ValidationIssue>,
+}
+
+impl<'d> Validator<'d> {
+    // members are the ones defined for the component (unknown if it is an undefined group)
+    fn component<V: AsRef<[u8]>>(
+        &mut self,
+        component: &FixComponent<V>,
+        members: Option<&[&'d [Member]]>,
+    ) {
+        let defined_tag = |tag| members.is_none_or(|members| is_member(members, tag));
+        let group_def = |no_tag| members.and_then(|members| find_defined_group(members, no_tag));
+        for entity in &component.entities {
+            match entity {
+                FixEntity::Field(tag, value) => {
+                    let value = String::from_utf8_lossy(value.as_ref()).into_owned();
+                    if !defined_tag(*tag) {
+                        self.report(IssueKind::UndefinedTag, *tag, value.clone());
+                    }
+                    // a NoXXX field which was not parsed as a group
+                    if let (Some(_), Ok(declared)) = (group_def(*tag), types::parse_int(&value)) {
+                        if declared != 0 {
+                            let kind = IssueKind::GroupCountMismatch {
+                                declared: declared as i32,
+                                instances: 0,
+                            };
+                            self.report(kind, *tag, value.clone());
+                        }
+                    }
+                    self.check_value(*tag, value);
+                }
+                FixEntity::Group(group) => {
+                    let value = group.repetitions.to_string();
+                    let group_def = group_def(group.no_tag);
+                    if !defined_tag(group.no_tag) {
+                        self.report(IssueKind::UndefinedTag, group.no_tag, value.clone());
+                    }
+                    if group.repetitions as usize != group.instances.len() {
+                        let kind = IssueKind::GroupCountMismatch {
+                            declared: group.repetitions,
+                            instances: group.instances.len(),
+                        };
+                        self.report(kind, group.no_tag, value);
+                    }
+                    for (index, instance) in group.instances.iter().enumerate() {
+                        self.groups.push((group.no_tag, index));
+                        match group_def {
+                            Some(group_def) => {
+                                self.component(instance, Some(&[&group_def.members]))
+                            }
+                            None => self.component(instance, None),
+                        }
+                        self.groups.pop();
+                    }
+                }
+            }
+        }
+
+        if let Some(members) = members {
+            let present: HashSet<i32> = component
+                .entities
+                .iter()
+                .map(|entity| match entity {
+                    FixEntity::Field(tag, _value) => *tag,
+                    FixEntity::Group(group) => group.no_tag,
+                })
+                .collect();
+            for members in members {
+                self.check_required(members, &present);
+            }
+        }
+    }
+
+    fn check_required(&mut self, members: &[Member], present: &HashSet<i32>) {
+        for member in members {
+            match member {
+                Member::Field { tag, required } if *required && !present.contains(tag) => {
+                    self.report(IssueKind::MissingRequiredField, *tag, String::new());
+                }
+                Member::Group(group) if group.required && !present.contains(&group.no_tag) => {
+                    self.report(IssueKind::MissingRequiredField, group.no_tag, String::new());
+                }
+                // the required members of an optional component are required only if the
+                // component is present
+                Member::Component {
+                    required, members, ..
+                } if *required || present.iter().any(|tag| has_member_tag(members, *tag)) => {
+                    self.check_required(members, present);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn check_value(&mut self, tag: i32, value: String) {
+        let field = match self.dictionary.field(tag) {
+            Some(field) => field,
+            None => return,
+        };
+        let field_type = field.field_type.to_ascii_uppercase();
+        if let Some(expected) = value_type(&field_type) {
+            if !is_valid(&value, expected) {
+                self.report(IssueKind::InvalidValue { expected }, tag, value);
+                return;
+            }
+        }
+        if field.values.is_empty() {
+            return;
+        }
+        let in_enum = |value: &str| {
+            field
+                .values
+                .iter()
+                .any(|field_value| field_value.value == value)
+        };
+        let valid = if field_type.starts_with("MULTIPLE") {
+            value.split(' ').all(in_enum)
+        } else {
+            in_enum(&value)
+        };
+        if !valid {
+            self.report(IssueKind::ValueNotInEnum, tag, value);
+        }
+    }
+
+    fn report(&mut self, kind: IssueKind, tag: i32, value: String) {
+        self.issues.push(ValidationIssue {
+            kind,
+            tag,
+            path: FixPath::from_indexes(&self.groups, tag),
+            value,
+        });
+    }
+}
+
+fn is_member(members: &[&[Member]], tag: i32) -> bool {
+    members.iter().any(|members| has_member_tag(members, tag))
+}
+
+fn find_defined_group<'d>(members: &[&'d [Member]], no_tag: i32) -> Option<&'d GroupDef> {
+    members
+        .iter()
+        .find_map(|members| find_group(members, no_tag))
+}
+
+// type of the values of the fields whose type has the given name (eg: PRICE, UTCTimestamp)
+fn value_type(field_type: &str) -> Option<ValueType> {
+    match field_type {
+        "INT" | "LENGTH" | "SEQNUM" | "NUMINGROUP" | "TAGNUM" | "DAYOFMONTH" => {
+            Some(ValueType::Int)
+        }
+        "FLOAT" | "PRICE" | "QTY" | "AMT" | "PRICEOFFSET" | "PERCENTAGE" => {
+            Some(ValueType::Decimal)
+        }
+        "CHAR" => Some(ValueType::Char),
+        "BOOLEAN" => Some(ValueType::Bool),
+        "UTCTIMESTAMP" => Some(ValueType::UtcTimestamp),
+        "LOCALMKTDATE" | "UTCDATEONLY" | "UTCDATE" => Some(ValueType::LocalMktDate),
+        "MONTHYEAR" => Some(ValueType::MonthYear),
+        _ => None,
+    }
+}
+
+fn is_valid(value: &str, value_type: ValueType) -> bool {
+    match value_type {
+        ValueType::Int => types::parse_int(value).is_ok(),
+        ValueType::Decimal => value.parse::<Decimal>().is_ok(),
+        ValueType::Char => types::parse_char(value).is_ok(),
+        ValueType::Bool => types::parse_bool(value).is_ok(),
+        ValueType::UtcTimestamp => value.parse::<UtcTimestamp>().is_ok(),
+        ValueType::LocalMktDate => value.parse::<LocalMktDate>().is_ok(),
+        ValueType::MonthYear => value.parse::<MonthYear>().is_ok(),
+    }
+}
@@ -0,0 +1,58 @@
+/// Result of the verification of the CheckSum (tag 10) of a message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChecksumCheck {
+    /// Value of tag 10 as found in the message.
+    pub declared: String,
+    /// Checksum computed over the message, as if SOH was the separator.
+    pub computed: u8,
+}
+
+impl ChecksumCheck {
+    /// The declared value matches the computed one (three digits, as required by FIX).
+    pub fn is_valid(&self) -> bool {
+        self.declared == format_checksum(self.computed)
+    }
+}
+
+/// Result of the verification of the BodyLength (tag 9) of a message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BodyLengthCheck {
+    /// Value of tag 9 as found in the message.
+    pub declared: String,
+    /// Number of bytes between tag 9 and tag 10, as if SOH was the separator.
+    pub computed: usize,
+}
+
+impl BodyLengthCheck {
+    /// The declared value matches the computed one.
+    pub fn is_valid(&self) -> bool {
+        self.declared.parse() == Ok(self.computed)
+    }
+}
+
+pub(crate) fn format_checksum(checksum: u8) -> String {
+    format!("{:03}", checksum)
+}
+
+// length and sum of the bytes of the fields of a message, as if SOH was the separator
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct FieldsSummary {
+    pub(crate) length: usize,
+    sum: u64,
+}
+
+impl FieldsSummary {
+    pub(crate) fn add(&mut self, field: &[u8], followed_by_separator: bool) {
+        const SOH: u64 = 1;
+        self.length += field.len();
+        self.sum += field.iter().map(|byte| u64::from(*byte)).sum::<u64>();
+        if followed_by_separator {
+            self.length += 1;
+            self.sum += SOH;
+        }
+    }
+
+    pub(crate) fn checksum(&self) -> u8 {
+        (self.sum % 256) as u8
+    }
+}
@@ -0,0 +1,296 @@
+use crate::error::{DeserializeError, FieldError};
+use crate::model::{self, FixComponent, FixEntity, FixGroup};
+use crate::query::FixPath;
+use crate::types::{self, Decimal, LocalMktDate, MonthYear, UtcTimestamp, ValueError, ValueType};
+use serde::{Deserialize, Serialize, Serializer};
+
+/// Read-only view of a list of fields and groups (eg: an instance of a repeating group or the
+/// header of a message).
+///
+/// `V` is the type in which the values are stored (`Vec<u8>` for [`FixMessage`](crate::FixMessage)
+/// and `&[u8]` for [`FixMessageRef`](crate::FixMessageRef)).
+pub struct ComponentView<'m, V = Vec<u8>> {
+    entities: &'m [FixEntity<V>],
+}
+
+impl<'m, V> Clone for ComponentView<'m, V> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'m, V> Copy for ComponentView<'m, V> {}
+
+impl<'m, V: AsRef<[u8]>> ComponentView<'m, V> {
+    pub(crate) fn new(component: &'m FixComponent<V>) -> Self {
+        Self::from_entities(&component.entities)
+    }
+
+    pub(crate) fn from_entities(entities: &'m [FixEntity<V>]) -> Self {
+        Self { entities }
+    }
+
+    pub(crate) fn entity_slice(&self) -> &'m [FixEntity<V>] {
+        self.entities
+    }
+
+    /// Get the value of the first field with the given tag.
+    ///
+    /// It is `None` if there is no such field or if its value is not valid UTF-8 (see
+    /// [`ComponentView::get_bytes`]). Fields inside repeating groups are not looked up.
+    pub fn get(&self, tag: i32) -> Option<&'m str> {
+        self.get_bytes(tag)
+            .and_then(|value| std::str::from_utf8(value).ok())
+    }
+
+    /// Get the value of the first field with the given tag, as raw bytes.
+    ///
+    /// Fields inside repeating groups are not looked up.
+    pub fn get_bytes(&self, tag: i32) -> Option<&'m [u8]> {
+        model::get_field(self.entities, tag).map(AsRef::as_ref)
+    }
+
+    /// Get the values of all the fields with the given tag (including the ones inside repeating
+    /// groups), in the order they appear.
+    ///
+    /// Values which are not valid UTF-8 are skipped (see [`ComponentView::get_all_bytes`]).
+    pub fn get_all(&self, tag: i32) -> Vec<&'m str> {
+        self.get_all_bytes(tag)
+            .into_iter()
+            .filter_map(|value| std::str::from_utf8(value).ok())
+            .collect()
+    }
+
+    /// Same as [`ComponentView::get_all`], but the values are returned as raw bytes.
+    pub fn get_all_bytes(&self, tag: i32) -> Vec<&'m [u8]> {
+        field_values(self.entities, tag)
+            .into_iter()
+            .map(AsRef::as_ref)
+            .collect()
+    }
+
+    /// Get the values selected by the given path (see [`FixPath`]), in the order they appear.
+    ///
+    /// Values which are not valid UTF-8 are skipped (see [`ComponentView::query_bytes`]).
+    pub fn query(&self, path: &FixPath) -> Vec<&'m str> {
+        self.query_bytes(path)
+            .into_iter()
+            .filter_map(|value| std::str::from_utf8(value).ok())
+            .collect()
+    }
+
+    /// Same as [`ComponentView::query`], but the values are returned as raw bytes.
+    pub fn query_bytes(&self, path: &FixPath) -> Vec<&'m [u8]> {
+        path.evaluate(self.entities)
+            .into_iter()
+            .map(AsRef::as_ref)
+            .collect()
+    }
+
+    /// Deserializes the fields and groups into a type which implements [`serde::Deserialize`].
+    ///
+    /// The view is deserialized as a map whose keys are the tags, so the fields of a struct are
+    /// matched with `#[serde(rename = "<tag>")]`. Repeating groups are deserialized as sequences
+    /// of their instances, and the values of the fields are parsed as the typed getters do (eg:
+    /// booleans are `Y` or `N`). See [`FixMessage::deserialize_into`](crate::FixMessage::deserialize_into).
+    ///
+    /// A group which was not detected (eg: a group with a single instance parsed without a
+    /// dictionary) can be deserialized into a sequence of structs anyway: the instances are made of
+    /// the fields after the NoXXX field which are members of the struct.
+    pub fn deserialize_into<T: Deserialize<'m>>(&self) -> Result<T, DeserializeError> {
+        T::deserialize(*self)
+    }
+
+    /// Get the repeating group whose NoXXX field has the given tag.
+    ///
+    /// Groups nested in other groups are not looked up.
+    pub fn group(&self, no_tag: i32) -> Option<GroupView<'m, V>> {
+        self.entities.iter().find_map(|entity| match entity {
+            FixEntity::Group(group) if group.no_tag == no_tag => Some(GroupView::new(group)),
+            _ => None,
+        })
+    }
+
+    /// Iterates over the fields and groups in the order they appear.
+    pub fn entities(&self) -> impl Iterator<Item = EntityView<'m, V>> {
+        self.entities.iter().map(EntityView::new)
+    }
+
+    /// Get the value of the first field with the given tag as an integer (eg: quantities, NoXXX
+    /// fields).
+    ///
+    /// It is `None` if there is no such field, and an error if the value can't be read as an
+    /// integer. Fields inside repeating groups are not looked up.
+    pub fn get_int(&self, tag: i32) -> Option<Result<i64, FieldError>> {
+        self.get_parsed(tag, ValueType::Int, types::parse_int)
+    }
+
+    /// Same as [`ComponentView::get_int`], but the value is read as a [`Decimal`] (eg: prices).
+    pub fn get_decimal(&self, tag: i32) -> Option<Result<Decimal, FieldError>> {
+        self.get_parsed(tag, ValueType::Decimal, str::parse)
+    }
+
+    /// Same as [`ComponentView::get_int`], but the value is read as a single character.
+    pub fn get_char(&self, tag: i32) -> Option<Result<char, FieldError>> {
+        self.get_parsed(tag, ValueType::Char, types::parse_char)
+    }
+
+    /// Same as [`ComponentView::get_int`], but the value is read as a boolean (`Y` or `N`).
+    pub fn get_bool(&self, tag: i32) -> Option<Result<bool, FieldError>> {
+        self.get_parsed(tag, ValueType::Bool, types::parse_bool)
+    }
+
+    /// Same as [`ComponentView::get_int`], but the value is read as a [`UtcTimestamp`] (eg:
+    /// SendingTime, TransactTime).
+    pub fn get_utc_timestamp(&self, tag: i32) -> Option<Result<UtcTimestamp, FieldError>> {
+        self.get_parsed(tag, ValueType::UtcTimestamp, str::parse)
+    }
+
+    /// Same as [`ComponentView::get_int`], but the value is read as a [`LocalMktDate`] (eg:
+    /// MaturityDate).
+    pub fn get_local_mkt_date(&self, tag: i32) -> Option<Result<LocalMktDate, FieldError>> {
+        self.get_parsed(tag, ValueType::LocalMktDate, str::parse)
+    }
+
+    /// Same as [`ComponentView::get_int`], but the value is read as a [`MonthYear`] (eg:
+    /// MaturityMonthYear).
+    pub fn get_month_year(&self, tag: i32) -> Option<Result<MonthYear, FieldError>> {
+        self.get_parsed(tag, ValueType::MonthYear, str::parse)
+    }
+
+    fn get_parsed<T>(
+        &self,
+        tag: i32,
+        expected: ValueType,
+        parse: impl Fn(&str) -> Result<T, ValueError>,
+    ) -> Option<Result<T, FieldError>> {
+        let value = self.get_bytes(tag)?;
+        let value = match std::str::from_utf8(value) {
+            Ok(value) => value,
+            Err(_) => return Some(Err(FieldError::InvalidUtf8 { tag })),
+        };
+        Some(parse(value).map_err(|error| {
+            let value = value.to_string();
+            match error {
+                ValueError::Invalid => FieldError::InvalidValue {
+                    tag,
+                    value,
+                    expected,
+                },
+                ValueError::OutOfRange => FieldError::OutOfRange {
+                    tag,
+                    value,
+                    expected,
+                },
+            }
+        }))
+    }
+}
+
+// values of the fields with the given tag (including the ones in groups), in the order they appear
+pub(crate) fn field_values<V>(entities: &[FixEntity<V>], tag: i32) -> Vec<&V> {
+    let mut values = Vec::new();
+    collect_values(entities, tag, &mut values);
+    values
+}
+
+fn collect_values<'m, V>(entities: &'m [FixEntity<V>], tag: i32, values: &mut Vec<&'m V>) {
+    for entity in entities {
+        match entity {
+            FixEntity::Field(field_tag, value) if *field_tag == tag => values.push(value),
+            FixEntity::Field(..) => {}
+            FixEntity::Group(group) => group
+                .instances
+                .iter()
+                .for_each(|instance| collect_values(&instance.entities, tag, values)),
+        }
+    }
+}
+
+// serialized as FixMessage (ie: a map whose keys are the tags)
+impl<'m, V: AsRef<[u8]>> Serialize for ComponentView<'m, V> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        model::serialize_entities(self.entities, serializer)
+    }
+}
+
+/// Read-only view of a repeating group.
+pub struct GroupView<'m, V = Vec<u8>> {
+    group: &'m FixGroup<V>,
+}
+
+impl<'m, V> Clone for GroupView<'m, V> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'m, V> Copy for GroupView<'m, V> {}
+
+impl<'m, V: AsRef<[u8]>> GroupView<'m, V> {
+    fn new(group: &'m FixGroup<V>) -> Self {
+        Self { group }
+    }
+
+    /// Tag of the NoXXX field (eg: 555 for NoLegs).
+    pub fn no_tag(&self) -> i32 {
+        self.group.no_tag
+    }
+
+    /// First tag of each instance.
+    pub fn delimiter(&self) -> i32 {
+        self.group.delimiter
+    }
+
+    /// Number of repetitions declared in the NoXXX field. It can differ from [`GroupView::len`] in
+    /// invalid messages.
+    pub fn declared_len(&self) -> i32 {
+        self.group.repetitions
+    }
+
+    /// Number of instances found in the message.
+    pub fn len(&self) -> usize {
+        self.group.instances.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.group.instances.is_empty()
+    }
+
+    /// Get the instance at the given index (starting at 0).
+    pub fn instance(&self, index: usize) -> Option<ComponentView<'m, V>> {
+        self.group.instances.get(index).map(ComponentView::new)
+    }
+
+    /// Iterates over the instances in the order they appear.
+    pub fn instances(&self) -> impl Iterator<Item = ComponentView<'m, V>> {
+        self.group.instances.iter().map(ComponentView::new)
+    }
+}
+
+/// A field or a repeating group, as found while iterating over a message.
+pub enum EntityView<'m, V = Vec<u8>> {
+    /// Tag and value of a field.
+    Field(i32, &'m [u8]),
+    Group(GroupView<'m, V>),
+}
+
+impl<'m, V: AsRef<[u8]>> EntityView<'m, V> {
+    fn new(entity: &'m FixEntity<V>) -> Self {
+        match entity {
+            FixEntity::Field(tag, value) => EntityView::Field(*tag, value.as_ref()),
+            FixEntity::Group(group) => EntityView::Group(GroupView::new(group)),
+        }
+    }
+
+    /// Tag of the field, or tag of the NoXXX field for groups.
+    pub fn tag(&self) -> i32 {
+        match self {
+            EntityView::Field(tag, _value) => *tag,
+            EntityView::Group(group) => group.no_tag(),
+        }
+    }
+}
@@ -0,0 +1,141 @@
+use crate::model::{FixComponent, FixEntity, FixGroup};
+
+/// Mutable view of a list of fields and groups (eg: an instance of a repeating group).
+///
+/// The NoXXX count of the groups is kept equal to the number of their instances.
+pub struct ComponentMut<'m> {
+    component: &'m mut FixComponent<Vec<u8>>,
+}
+
+impl<'m> ComponentMut<'m> {
+    pub(crate) fn new(component: &'m mut FixComponent<Vec<u8>>) -> Self {
+        Self { component }
+    }
+
+    /// Sets the value of the first field with the given tag. If there is no such field, it is
+    /// added at the end (before the CheckSum, if any). Returns `false` (and nothing is changed)
+    /// if the tag is the NoXXX field of a group, as its count is the number of instances.
+    pub fn set(&mut self, tag: i32, value: impl Into<Vec<u8>>) -> bool {
+        let value = value.into();
+        match self
+            .position(tag)
+            .map(|index| &mut self.component.entities[index])
+        {
+            Some(FixEntity::Field(_tag, field_value)) => *field_value = value,
+            Some(FixEntity::Group(_group)) => return false,
+            None => {
+                let index = self.position(10).unwrap_or(self.component.entities.len());
+                self.component
+                    .entities
+                    .insert(index, FixEntity::Field(tag, value));
+            }
+        }
+        true
+    }
+
+    /// Removes the first field with the given tag (or the group whose NoXXX field has the given
+    /// tag). Returns `false` if there is no such field or group.
+    pub fn remove(&mut self, tag: i32) -> bool {
+        match self.position(tag) {
+            Some(index) => {
+                self.component.entities.remove(index);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Inserts a field after the first field with the tag `anchor_tag` (or after the group whose
+    /// NoXXX field has that tag). Returns `false` if the anchor is not found.
+    pub fn insert_after(&mut self, anchor_tag: i32, tag: i32, value: impl Into<Vec<u8>>) -> bool {
+        match self.position(anchor_tag) {
+            Some(index) => {
+                self.component
+                    .entities
+                    .insert(index + 1, FixEntity::Field(tag, value.into()));
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Appends an empty instance to the group whose NoXXX field has the given tag, and returns it
+    /// so its fields can be set. The instance is counted even if none of its fields is set.
+    ///
+    /// It is `None` if there is no such group (eg: a group with a single instance parsed without a
+    /// dictionary). Groups nested in other groups are not looked up.
+    pub fn push_group_instance(&mut self, no_tag: i32) -> Option<ComponentMut<'_>> {
+        push_group_instance(self.component, no_tag)
+    }
+
+    /// Removes the instance at the given index (starting at 0) of the group whose NoXXX field has
+    /// the given tag. Returns `false` if there is no such group or instance.
+    ///
+    /// The group is kept even if it has no instances left (it is omitted when the message is
+    /// encoded to tag=value).
+    pub fn remove_group_instance(&mut self, no_tag: i32, index: usize) -> bool {
+        match group_mut(self.component, no_tag) {
+            Some(group) if index < group.instances.len() => {
+                group.instances.remove(index);
+                update_repetitions(group);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Get the instance at the given index (starting at 0) of the group whose NoXXX field has the
+    /// given tag, so its fields can be changed.
+    pub fn group_instance_mut(&mut self, no_tag: i32, index: usize) -> Option<ComponentMut<'_>> {
+        group_instance_mut(self.component, no_tag, index)
+    }
+
+    // index of the first field with the given tag, or of the group with the given NoXXX tag
+    fn position(&self, tag: i32) -> Option<usize> {
+        self.component
+            .entities
+            .iter()
+            .position(|entity| match entity {
+                FixEntity::Field(field_tag, _value) => *field_tag == tag,
+                FixEntity::Group(group) => group.no_tag == tag,
+            })
+    }
+}
+
+// the functions which return a ComponentMut borrow the component directly (instead of
+// ComponentMut) so FixMessage can return what they return
+
+pub(crate) fn push_group_instance(
+    component: &mut FixComponent<Vec<u8>>,
+    no_tag: i32,
+) -> Option<ComponentMut<'_>> {
+    let group = group_mut(component, no_tag)?;
+    group.instances.push(FixComponent::new(Vec::new()));
+    update_repetitions(group);
+    group.instances.last_mut().map(ComponentMut::new)
+}
+
+pub(crate) fn group_instance_mut(
+    component: &mut FixComponent<Vec<u8>>,
+    no_tag: i32,
+    index: usize,
+) -> Option<ComponentMut<'_>> {
+    group_mut(component, no_tag)?
+        .instances
+        .get_mut(index)
+        .map(ComponentMut::new)
+}
+
+fn group_mut(component: &mut FixComponent<Vec<u8>>, no_tag: i32) -> Option<&mut FixGroup<Vec<u8>>> {
+    component
+        .entities
+        .iter_mut()
+        .find_map(|entity| match entity {
+            FixEntity::Group(group) if group.no_tag == no_tag => Some(group),
+            _ => None,
+        })
+}
+
+fn update_repetitions(group: &mut FixGroup<Vec<u8>>) {
+    group.repetitions = group.instances.len() as i32;
+}
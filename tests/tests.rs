@@ -1,4 +1,14 @@
-use fixparser::FixMessage;
+// the tests written for the first versions pass &input (a &&str) to from_tag_value
+#![allow(clippy::needless_borrow)]
+
+use fixparser::{
+    BodyLengthCheck, ChecksumCheck, Decimal, Direction, EntityView, FieldError, FixMessage,
+    FixMessageBuilder, FixMessageRef, FixPath, FixStreamParser, JsonError, LocalMktDate, LogFormat,
+    LogFormatError, LogPrefix, MonthYear, ParseError, ParseWarning, ParserOptions, PathError,
+    UtcTimestamp, ValueType, WarningKind,
+};
+#[cfg(feature = "dictionary")]
+use fixparser::{Dictionary, DictionaryError, IssueKind, Member, ValidationIssue};
 
 #[test]
 fn minimal_length() {
@@ -77,6 +87,50 @@ fn soh_separator() {
     assert_eq!(output, FixMessage::from_tag_value(&input).unwrap().to_json().to_string());
 }
 
+#[test]
+fn non_standard_begin_strings() {
+    for (input, begin_string) in &[("8=FIX.5.0SP2\x019=5\x0135=0\x0110=209", "FIX.5.0SP2"), ("8=FIX.5.0SP2 | 9=5 | 35=0 | 10=209", "FIX.5.0SP2"), ("8=FIX.4.2-VENUE;9=5;35=0;10=209", "FIX.4.2-VENUE"), ("8=FIXT.1.1^A9=5^A35=0^A10=209", "FIXT.1.1")] {
+        let fix_message = FixMessage::from_tag_value(&input).unwrap();
+        assert_eq!((Some(*begin_string), Some("0"), Some("209")), (fix_message.get(8), fix_message.get(35), fix_message.get(10)), "{}", input);
+        assert!(fix_message.warnings().is_empty(), "{}", input);
+    }
+}
+
+#[test]
+fn literal_soh_separator() {
+    // eg: copied from a log which escapes non-printable characters
+    let input = r"8=FIX.4.4\x019=5\x0135=0\x0110=163\x01";
+    let output = r#"{"8":"FIX.4.4","9":"5","35":"0","10":"163"}"#;
+    let options = ParserOptions::new().verify_checksum(true);
+    let fix_message = FixMessage::from_tag_value_with(input, &options).unwrap();
+    assert_eq!(output, fix_message.to_json().to_string());
+    assert!(fix_message.checksum().unwrap().is_valid());
+}
+
+#[test]
+fn explicit_separator() {
+    // the value of tag 58 has the separator which would be detected
+    let input = "8=FIX.4.4 | 58=a ; b | 10=209";
+    let output = r#"{"8":"FIX.4.4","58":"a ; b","10":"209"}"#;
+    assert_eq!(output, FixMessage::from_tag_value_with(input, &ParserOptions::new().separator("|")).unwrap().to_json().to_string());
+    assert_eq!(output, FixMessage::from_tag_value_with(input, &ParserOptions::new().separator(b"|")).unwrap().to_json().to_string());
+
+    let input = "8=FIX.4.4^A35=0^A58=a|b^A10=209";
+    assert_eq!(Some("a|b"), FixMessage::from_tag_value_with(input, &ParserOptions::new().separator("^A")).unwrap().get(58));
+    assert_eq!(Err(ParseError::MissingSeparator { offset: 13 }), FixMessage::from_tag_value_with(input, &ParserOptions::new().separator("\x01")).map(|_| ()));
+
+    // the first of the allowed separators found after the begin string is used
+    let options = ParserOptions::new().allowed_separators(&["\x01", "|"]);
+    assert_eq!(Some("0"), FixMessage::from_tag_value_with("8=FIX.4.4\x0135=0\x0110=209", &options).unwrap().get(35));
+    assert_eq!(Some("0"), FixMessage::from_tag_value_with("8=FIX.4.4 | 35=0 | 10=209", &options).unwrap().get(35));
+    assert!(matches!(FixMessage::from_tag_value_with("8=FIX.4.4;35=0;10=209", &options), Err(ParseError::MissingSeparator { .. })));
+
+    // the stream parser uses the options too
+    let input = "8=FIX.4.4 | 58=a ; b | 10=209 | 8=FIX.4.4 | 58=c ; d | 10=209";
+    let messages = FixStreamParser::with_options(input.as_bytes(), ParserOptions::new().separator("|")).map(|message| message.unwrap().get(58).map(String::from)).collect::<Vec<_>>();
+    assert_eq!(vec![Some(String::from("a ; b")), Some(String::from("c ; d"))], messages);
+}
+
 // invalid still parsable messages
 
 #[test]
@@ -146,3 +200,1144 @@ fn missing_fix_version_2() {
     let input = "8= | 9=somethinghere | 10=123";
     FixMessage::from_tag_value(&input).unwrap().to_json().to_string();
 }
+
+#[test]
+fn error_missing_begin_string() {
+    let input = "9=12 | 35=A | 10=209";
+    assert_eq!(Err(ParseError::MissingBeginString { offset: 0 }), FixMessage::from_tag_value(input).map(|_| ()));
+}
+
+#[test]
+fn error_too_short() {
+    let input = "Recv | 8=FIX.4.4|1";
+    assert_eq!(Err(ParseError::TooShort { offset: 7, length: 11 }), FixMessage::from_tag_value(input).map(|_| ()));
+}
+
+#[test]
+fn error_invalid_begin_string() {
+    let input = "8= | 9=somethinghere | 10=123";
+    assert_eq!(Err(ParseError::InvalidBeginString { offset: 0 }), FixMessage::from_tag_value(input).map(|_| ()));
+}
+
+#[test]
+fn error_invalid_group_count() {
+    let input = "8=FIX.4.4 | 555=two | 600=A | 600=B | 10=209";
+    let error = ParseError::InvalidGroupCount { tag: 555, value: String::from("two"), offset: 30 };
+    let options = ParserOptions::new().reject_invalid_groups(true);
+    assert_eq!(Err(error), FixMessage::from_tag_value_with(input, &options).map(|_| ()));
+}
+
+#[test]
+fn error_missing_group_count() {
+    let input = "8=FIX.4.4 | 8=FIX.4.4 | 10=209";
+    let options = ParserOptions::new().reject_invalid_groups(true);
+    assert_eq!(Err(ParseError::MissingGroupCount { tag: 8, offset: 12 }), FixMessage::from_tag_value_with(input, &options).map(|_| ()));
+}
+
+// warnings
+
+#[test]
+fn warnings_ignored_tokens() {
+    let input = "8=FIX.4.4 | 9=some | thing=wrong | garbage | 58= | 10=209abc | 11=some";
+    let warnings = FixMessage::from_tag_value(input).unwrap().warnings().to_vec();
+    assert_eq!(
+        vec![
+            ParseWarning { kind: WarningKind::InvalidTag, tag: None, offset: 21, token: String::from("thing=wrong") },
+            ParseWarning { kind: WarningKind::IgnoredToken, tag: None, offset: 35, token: String::from("garbage") },
+            ParseWarning { kind: WarningKind::EmptyValue, tag: Some(58), offset: 45, token: String::from("58=") },
+            ParseWarning { kind: WarningKind::CharactersAfterChecksum, tag: Some(10), offset: 57, token: String::from("abc") },
+            ParseWarning { kind: WarningKind::TagAfterChecksum, tag: None, offset: 63, token: String::from("11=some") },
+        ],
+        warnings
+    );
+}
+
+#[test]
+fn warnings_missing_checksum_and_repetition() {
+    let input = "8=FIX.4.4 | 555=3 | 600=QWE | 600=RTY";
+    let warnings = FixMessage::from_tag_value(input).unwrap().warnings().to_vec();
+    assert_eq!(vec![ParseWarning { kind: WarningKind::MissingChecksum, tag: Some(10), offset: 37, token: String::new() }], warnings);
+
+    let input = "8=FIX.4.4 | 555=3 | 600=QWE | 600=RTY | 10=209";
+    let warnings = FixMessage::from_tag_value(input).unwrap().warnings().to_vec();
+    assert_eq!(vec![ParseWarning { kind: WarningKind::EndOfMessageInGroup, tag: Some(555), offset: 40, token: String::from("10=209") }], warnings);
+}
+
+#[test]
+fn warnings_duplicate_tags() {
+    // a repeated tag is a group whose NoXXX field is the one before it, the instances it doesn't declare are duplicates
+    let input = "8=FIX.4.4 | 555=two | 600=A | 600=B | 10=209";
+    let message = FixMessage::from_tag_value(input).unwrap();
+    assert_eq!(r#"{"8":"FIX.4.4","555":[{"600":"A"},{"600":"B"}],"10":"209"}"#, message.to_json().to_string());
+    let warnings = vec![
+        ParseWarning { kind: WarningKind::InvalidGroupCount { value: String::from("two") }, tag: Some(555), offset: 30, token: String::from("600=B") },
+        ParseWarning { kind: WarningKind::DuplicateTag, tag: Some(600), offset: 30, token: String::from("600=B") },
+    ];
+    assert_eq!(warnings, message.warnings().to_vec());
+    let message = FixMessage::from_tag_value("8=FIX.4.4 | 555=-1 | 600=A | 600=B | 10=209").unwrap();
+    assert_eq!(vec![(WarningKind::InvalidGroupCount { value: String::from("-1") }, Some(555), 29), (WarningKind::DuplicateTag, Some(600), 29)], warnings_of(&message));
+    let message = FixMessage::from_tag_value("8=FIX.4.4 | 555=0 | 600=A | 600=B | 10=209").unwrap();
+    assert_eq!(vec![(WarningKind::DuplicateTag, Some(600), 28)], warnings_of(&message));
+
+    let input = "8=FIX.4.4 | 9=5 | 35=0 | 58=a | 58=b | 10=209";
+    let message = FixMessage::from_tag_value(input).unwrap();
+    assert_eq!(r#"{"8":"FIX.4.4","9":"5","35":[{"58":"a"},{"58":"b"}],"10":"209"}"#, message.to_json().to_string());
+    assert_eq!(vec![(WarningKind::DuplicateTag, Some(58), 32)], warnings_of(&message));
+
+    // without a field before it, the tag is kept as a duplicate
+    let input = "8=FIX.4.4 | 8=FIX.4.4 | 10=209";
+    let message = FixMessage::from_tag_value(input).unwrap();
+    assert_eq!(vec![(WarningKind::MissingGroupCount, Some(8), 12), (WarningKind::DuplicateTag, Some(8), 12)], warnings_of(&message));
+}
+
+#[test]
+fn no_warnings() {
+    let input = "8=FIX.4.4 | 555=2 | 600=CGY | 604=2 | 605=F7 | 605=CGYU0 | 600=CGY | 10=209 | ";
+    assert!(FixMessage::from_tag_value(input).unwrap().warnings().is_empty());
+}
+
+// strict and lenient modes
+
+fn warnings_of(message: &FixMessage) -> Vec<(WarningKind, Option<i32>, usize)> {
+    message.warnings().iter().map(|warning| (warning.kind.clone(), warning.tag, warning.offset)).collect()
+}
+
+fn rejected_warning(input: &str, options: &ParserOptions) -> Option<(WarningKind, Option<i32>, usize)> {
+    match FixMessage::from_tag_value_with(input, options) {
+        Err(ParseError::Rejected { warning }) => Some((warning.kind, warning.tag, warning.offset)),
+        _ => None,
+    }
+}
+
+#[test]
+fn strict_mode() {
+    let valid = FixMessageBuilder::new("FIX.4.4", "D").field(11, "order").group(453, |parties| parties.instance(|party| party.field(448, "A")).instance(|party| party.field(448, "B"))).build().to_tag_value();
+    let valid = String::from_utf8(valid).unwrap();
+    assert!(FixMessage::from_tag_value_with(&valid, &ParserOptions::strict()).is_ok());
+
+    let inputs = [
+        ("8=FIX.4.4 | 9=5 | 35=0 | thing=wrong | 10=163", WarningKind::InvalidTag),
+        ("8=FIX.4.4 | 9=5 | 35=0 | garbage | 10=163", WarningKind::IgnoredToken),
+        ("8=FIX.4.4 | 9=5 | 35=0 | 10=163 | 11=after checksum", WarningKind::TagAfterChecksum),
+        ("8=FIX.4.4 | 9=5 | 35=0 | 10=163abc", WarningKind::CharactersAfterChecksum),
+        ("8=FIX.4.4 | 9=5 | 35=0 | 10=209", WarningKind::ChecksumMismatch { declared: String::from("209"), computed: String::from("163") }),
+        ("8=FIX.4.4 | 9=6 | 35=0 | 10=164", WarningKind::BodyLengthMismatch { declared: String::from("6"), computed: 5 }),
+        ("8=FIX.4.4 | 9=5 | 35=0", WarningKind::MissingChecksum),
+    ];
+    for (input, kind) in inputs.iter() {
+        // the lenient mode (ie: the default) parses the message anyway
+        let options = ParserOptions::lenient().verify_checksum(true).verify_body_length(true);
+        let fix_message = FixMessage::from_tag_value_with(input, &options).unwrap();
+        assert!(fix_message.warnings().iter().any(|warning| warning.kind == *kind), "{}", input);
+        assert!(rejected_warning(input, &ParserOptions::strict()).is_some(), "{}", input);
+    }
+
+    // the first anomaly of the message is the one reported
+    let input = "8=FIX.4.4 | 9=5 | 35=0 | 58= | garbage | 10=209";
+    let options = ParserOptions::new().reject_all_warnings(true);
+    assert_eq!(Some((WarningKind::EmptyValue, Some(58), 25)), rejected_warning(input, &options));
+    assert_eq!(Some(String::from("message rejected at offset 25: Tag 58 has no value")), FixMessage::from_tag_value_with(input, &options).err().map(|error| error.to_string()));
+    assert_eq!(Some((WarningKind::BodyLengthMismatch { declared: String::from("5"), computed: 17 }, Some(9), 12)), rejected_warning(input, &ParserOptions::strict()));
+}
+
+#[test]
+fn strict_rules() {
+    // each rule can be enabled on its own, and the other anomalies are still warnings
+    let options = ParserOptions::new().require_standard_header(true);
+    assert_eq!(Some((WarningKind::MisplacedHeaderTag { expected: 9 }, Some(35), 12)), rejected_warning("8=FIX.4.4 | 35=0 | 9=5 | 10=163", &options));
+    assert_eq!(Some((WarningKind::MisplacedHeaderTag { expected: 35 }, None, 18)), rejected_warning("8=FIX.4.4 | 9=5 | ", &options));
+    assert!(FixMessage::from_tag_value_with("8=FIX.4.4 | 9=5 | 35=0 | garbage", &options).is_ok());
+
+    let options = ParserOptions::new().reject_duplicate_tags(true);
+    assert_eq!(Some((WarningKind::DuplicateTag, Some(58), 32)), rejected_warning("8=FIX.4.4 | 9=5 | 35=0 | 58=a | 58=b | 10=209", &options));
+    assert_eq!(Some((WarningKind::DuplicateTag, Some(58), 32)), rejected_warning("8=FIX.4.4 | 9=5 | 49=X | 58=a | 58=b | 10=209", &options));
+    assert_eq!(Some((WarningKind::DuplicateTag, Some(448), 35)), rejected_warning("8=FIX.4.4 | 35=0 | 453=1 | 448=A | 448=B | 10=209", &options));
+    assert!(FixMessage::from_tag_value_with("8=FIX.4.4 | 35=0 | 453=2 | 448=A | 448=B | 10=209 | garbage", &options).is_ok());
+    #[cfg(feature = "dictionary")]
+    assert!(FixMessage::from_tag_value_with_dictionary("8=FIX.4.4 | 35=0 | 58=a | 58=b | 10=209", &fix44_dictionary()).is_ok());
+
+    let options = ParserOptions::new().require_checksum(true);
+    assert_eq!(Some((WarningKind::MissingChecksum, Some(10), 25)), rejected_warning("8=FIX.4.4 | 9=5 | 35=0 | ", &options));
+    assert!(FixMessage::from_tag_value_with("8=FIX.4.4 | 9=5 | 35=0 | 10=209 | 11=after checksum", &options).is_ok());
+
+    let options = ParserOptions::new().reject_empty_values(true);
+    assert_eq!(Some((WarningKind::EmptyValue, Some(58), 25)), rejected_warning("8=FIX.4.4 | 9=5 | 35=0 | 58= | 10=209", &options));
+    assert!(FixMessage::from_tag_value_with("8=FIX.4.4 | 9=5 | 35=0", &options).is_ok());
+
+    // the strict mode with a rule disabled
+    let options = ParserOptions::strict().reject_all_warnings(false).require_checksum(false);
+    assert!(FixMessage::from_tag_value_with("8=FIX.4.4 | 9=5 | 35=0 | garbage", &options).is_ok());
+    assert!(rejected_warning("8=FIX.4.4 | 9=5 | 35=0 | 58= | garbage", &options).is_some());
+}
+
+// checksum verification
+
+#[test]
+fn checksum_soh_separator() {
+    let input = "8=FIX.4.4\x019=67\x0135=A\x0149=SENDER\x0156=TARGET\x0134=1\x0152=20200520-19:15:45.134\x0198=0\x01108=30\x0110=135\x01";
+    let options = ParserOptions::new().verify_checksum(true);
+    let fix_message = FixMessage::from_tag_value_with(input, &options).unwrap();
+    assert_eq!(Some(&ChecksumCheck { declared: String::from("135"), computed: 135 }), fix_message.checksum());
+    assert!(fix_message.warnings().is_empty());
+}
+
+#[test]
+fn checksum_other_separators() {
+    let options = ParserOptions::new().verify_checksum(true);
+    for input in &["8=FIX.4.4|9=5|35=0|10=163", "Recv | 8=FIX.4.4 | 9=5 | 35=0 | 10=163 | ", "8=FIX.4.4^A9=5^A35=0^A10=163^A"] {
+        assert!(FixMessage::from_tag_value_with(input, &options).unwrap().checksum().unwrap().is_valid());
+    }
+}
+
+#[test]
+fn checksum_mismatch() {
+    let input = "8=FIX.4.4 | 9=5 | 35=0 | 10=209";
+    assert_eq!(None, FixMessage::from_tag_value(input).unwrap().checksum());
+
+    let options = ParserOptions::new().verify_checksum(true);
+    let fix_message = FixMessage::from_tag_value_with(input, &options).unwrap();
+    assert!(!fix_message.checksum().unwrap().is_valid());
+    let kind = WarningKind::ChecksumMismatch { declared: String::from("209"), computed: String::from("163") };
+    assert_eq!(vec![ParseWarning { kind, tag: Some(10), offset: 25, token: String::from("10=209") }], fix_message.warnings());
+}
+
+// body length verification
+
+#[test]
+fn body_length() {
+    let options = ParserOptions::new().verify_body_length(true);
+    let input = "8=FIX.4.4\x019=67\x0135=A\x0149=SENDER\x0156=TARGET\x0134=1\x0152=20200520-19:15:45.134\x0198=0\x01108=30\x0110=135\x01";
+    assert_eq!(Some(&BodyLengthCheck { declared: String::from("67"), computed: 67 }), FixMessage::from_tag_value_with(input, &options).unwrap().body_length());
+
+    let input = "8=FIX.4.4 | 9=005 | 35=0 | 10=163";
+    assert!(FixMessage::from_tag_value_with(input, &options).unwrap().body_length().unwrap().is_valid());
+    assert_eq!(None, FixMessage::from_tag_value(input).unwrap().body_length());
+}
+
+#[test]
+fn body_length_mismatch() {
+    let options = ParserOptions::new().verify_body_length(true);
+    // truncated message
+    let input = "8=FIX.4.4 | 9=20 | 35=0";
+    let fix_message = FixMessage::from_tag_value_with(input, &options).unwrap();
+    let kind = WarningKind::BodyLengthMismatch { declared: String::from("20"), computed: 4 };
+    assert_eq!(ParseWarning { kind, tag: Some(9), offset: 12, token: String::from("9=20") }, fix_message.warnings()[0]);
+
+    // unexpected fields
+    let input = "8=FIX.4.4|9=5|35=0|58=text|10=163";
+    let fix_message = FixMessage::from_tag_value_with(input, &options).unwrap();
+    assert_eq!(Some(&BodyLengthCheck { declared: String::from("5"), computed: 13 }), fix_message.body_length());
+}
+
+// data fields
+
+#[test]
+fn data_field_with_separator() {
+    let input = "8=FIX.4.4 | 95=13 | 96=a | b=c | d=e | 58=text | 10=209";
+    let output = r#"{"8":"FIX.4.4","95":"13","96":"a | b=c | d=e","58":"text","10":"209"}"#;
+    let fix_message = FixMessage::from_tag_value(input).unwrap();
+    assert_eq!(output, fix_message.to_json().to_string());
+    assert!(fix_message.warnings().is_empty());
+}
+
+#[test]
+fn xml_data_field() {
+    let input = "8=FIX.4.4\x01212=14\x01213=<a b=\"1\">\x01</a>\x0110=209";
+    let output = r#"{"8":"FIX.4.4","212":"14","213":"<a b=\"1\">\u0001</a>","10":"209"}"#;
+    assert_eq!(output, FixMessage::from_tag_value(input).unwrap().to_json().to_string());
+}
+
+#[test]
+fn custom_data_field() {
+    let input = "8=FIX.4.4 | 5000=5 | 5001=x | y | 10=209";
+    let options = ParserOptions::new().data_field(5000, 5001);
+    let output = r#"{"8":"FIX.4.4","5000":"5","5001":"x | y","10":"209"}"#;
+    assert_eq!(output, FixMessage::from_tag_value_with(input, &options).unwrap().to_json().to_string());
+}
+
+#[test]
+fn data_field_with_wrong_length() {
+    let input = "8=FIX.4.4 | 95=4 | 96=a | b | 10=209";
+    let output = r#"{"8":"FIX.4.4","95":"4","96":"a","10":"209"}"#;
+    let fix_message = FixMessage::from_tag_value(input).unwrap();
+    assert_eq!(output, fix_message.to_json().to_string());
+    let kind = WarningKind::InvalidDataLength { length: 4 };
+    assert_eq!(ParseWarning { kind, tag: Some(96), offset: 19, token: String::from("96=a") }, fix_message.warnings()[0]);
+
+    let input = "8=FIX.4.4|95=18446744073709551615|96=a|10=000";
+    let fix_message = FixMessage::from_tag_value(input).unwrap();
+    assert_eq!(Some("a"), fix_message.get(96));
+    let kind = WarningKind::InvalidDataLength { length: u64::MAX as usize };
+    assert_eq!(ParseWarning { kind, tag: Some(96), offset: 34, token: String::from("96=a") }, fix_message.warnings()[0]);
+}
+
+#[test]
+fn data_field_checksum_and_body_length() {
+    let input = "8=FIX.4.4|9=12|95=3|96=a|b|10=198";
+    let options = ParserOptions::new().verify_checksum(true).verify_body_length(true);
+    let fix_message = FixMessage::from_tag_value_with(input, &options).unwrap();
+    assert_eq!(Some(&BodyLengthCheck { declared: String::from("12"), computed: 12 }), fix_message.body_length());
+    assert_eq!(Some(&ChecksumCheck { declared: String::from("198"), computed: 198 }), fix_message.checksum());
+}
+
+// byte input
+
+#[test]
+fn from_bytes_same_as_from_tag_value() {
+    let input = "8=FIX.4.4 | 555=2 | 600=CGY | 604=2 | 605=F7 | 605=CGYU0 | 600=CGY | 10=209";
+    let output = FixMessage::from_tag_value(input).unwrap().to_json();
+    assert_eq!(output, FixMessage::from_bytes(input.as_bytes()).unwrap().to_json());
+}
+
+#[test]
+fn from_bytes_non_utf8_values() {
+    // 58 is encoded in Shift-JIS
+    let input = b"8=FIX.4.4\x01347=Shift_JIS\x0158=\x93\xfa\x96\x7b\x0195=4\x0196=\x00\x01\xff=\x0110=209";
+    let fix_message = FixMessage::from_bytes(input).unwrap();
+    assert_eq!(Some(&b"\x93\xfa\x96\x7b"[..]), fix_message.get_bytes(58));
+    assert_eq!(Some(&b"\x00\x01\xff="[..]), fix_message.get_bytes(96));
+    assert!(fix_message.get_str(58).unwrap().is_err());
+    assert_eq!(Some("Shift_JIS"), fix_message.get_str(347).unwrap().ok());
+    assert_eq!("\u{FFFD}\u{FFFD}\u{FFFD}{", fix_message.get_lossy(58).unwrap());
+    assert_eq!(None, fix_message.get_bytes(11));
+    assert_eq!(r#"{"8":"FIX.4.4","347":"Shift_JIS","58":"���{","95":"4","96":"\u0000\u0001�=","10":"209"}"#, fix_message.to_json().to_string());
+}
+
+// borrowed messages
+
+#[test]
+fn message_ref_same_as_owned() {
+    let input = "8=FIX.4.4 | 9=01944 | 35=8 | 453=2 | 448=1 | 452=205 | 447=D | 448=FIX_OUT | 452=83 | 447=D | 555=2 | 600=CGY | 604=2 | 605=F7 | 605=CGYU0 | 600=CGY | 10=209 | 11=some";
+    let fix_message = FixMessage::from_tag_value(input).unwrap();
+    let fix_message_ref = FixMessageRef::from_tag_value(input).unwrap();
+    assert_eq!(fix_message.to_json(), fix_message_ref.to_json());
+    assert_eq!(fix_message.warnings(), fix_message_ref.warnings());
+    assert_eq!(fix_message.to_json(), fix_message_ref.to_owned().to_json());
+}
+
+#[test]
+fn message_ref_borrows_input() {
+    let input = b"8=FIX.4.4|58=\xff|10=209".to_vec();
+    let value = {
+        let fix_message_ref = FixMessageRef::from_bytes(&input).unwrap();
+        fix_message_ref.get_bytes(58).unwrap()
+    };
+    assert_eq!(input[13..14].as_ptr(), value.as_ptr());
+
+    // also the values of groups
+    let input = "8=FIX.4.4 | 555=2 | 600=A | 604=2 | 605=F7 | 605=F8 | 600=B | 10=209";
+    let (all, queried) = {
+        let fix_message_ref = FixMessageRef::from_tag_value(input).unwrap();
+        (fix_message_ref.get_all(600), fix_message_ref.query(&"555[0].604[*].605".parse().unwrap()))
+    };
+    assert_eq!((vec!["A", "B"], vec!["F7", "F8"]), (all, queried));
+}
+
+// read API
+
+#[test]
+fn read_fields_and_groups() {
+    let input = "8=FIX.4.4 | 35=AB | 555=3 | 600=A | 604=2 | 605=F7 | 605=CGYU0 | 600=B | 600=C | 604=2 | 605=X | 605=Y | 10=209";
+    let fix_message = FixMessage::from_tag_value(input).unwrap();
+    assert_eq!(Some("AB"), fix_message.get(35));
+    assert_eq!(None, fix_message.get(600));
+    assert_eq!(vec!["A", "B", "C"], fix_message.get_all(600));
+
+    let legs = fix_message.group(555).unwrap();
+    assert_eq!((555, 600, 3, 3), (legs.no_tag(), legs.delimiter(), legs.declared_len(), legs.len()));
+    let instances: Vec<_> = legs.instances().map(|instance| instance.get(600).unwrap()).collect();
+    assert_eq!(vec!["A", "B", "C"], instances);
+    assert_eq!(Some("X"), legs.instance(2).unwrap().group(604).unwrap().instance(0).unwrap().get(605));
+    assert!(legs.instance(1).unwrap().group(604).is_none());
+    assert!(legs.instance(3).is_none());
+    assert!(fix_message.group(604).is_none());
+}
+
+#[test]
+fn iterate_entities() {
+    let input = "8=FIX.4.4 | 453=2 | 448=A | 448=B | 58=\u{00f1} | 10=209";
+    let fix_message = FixMessage::from_tag_value(input).unwrap();
+    let tags: Vec<i32> = fix_message.entities().map(|entity| entity.tag()).collect();
+    assert_eq!(vec![8, 453, 58, 10], tags);
+    match fix_message.entities().nth(2).unwrap() {
+        EntityView::Field(tag, value) => assert_eq!((58, "ñ".as_bytes()), (tag, value)),
+        EntityView::Group(_) => panic!("A field was expected"),
+    }
+
+    let fix_message_ref = FixMessageRef::from_tag_value(input).unwrap();
+    assert_eq!(Some("ñ"), fix_message_ref.get(58));
+    assert_eq!(vec!["A", "B"], fix_message_ref.get_all(448));
+    assert_eq!(2, fix_message_ref.group(453).unwrap().len());
+}
+
+// typed getters
+
+#[test]
+fn typed_getters() {
+    let input = "8=FIX.4.4 | 35=8 | 34=3951 | 54=1 | 44=2900.0050 | 6=-0.5 | 1028=N | 200=202007 | 541=20200701 | 555=2 | 600=3D | 610=202007w2 | 600=3D | 610=20200730 | 10=139";
+    let fix_message = FixMessage::from_tag_value(input).unwrap();
+    assert_eq!(Some(Ok(3951)), fix_message.get_int(34));
+    assert_eq!(Some(Ok('1')), fix_message.get_char(54));
+    assert_eq!(Some(Ok(Decimal::new(29_000_050, 4))), fix_message.get_decimal(44));
+    assert_eq!("2900.0050", fix_message.get_decimal(44).unwrap().unwrap().to_string());
+    assert_eq!(Some(Ok(Decimal::new(-5, 1))), fix_message.get_decimal(6));
+    assert_eq!(Some(Ok(Decimal::new(3951, 0))), fix_message.get_decimal(34));
+    assert_eq!(Some(Ok(false)), fix_message.get_bool(1028));
+    assert_eq!(Some(Ok(MonthYear { year: 2020, month: 7, day: None, week: None })), fix_message.get_month_year(200));
+    assert_eq!(Some(Ok(LocalMktDate { year: 2020, month: 7, day: 1 })), fix_message.get_local_mkt_date(541));
+    assert_eq!(None, fix_message.get_int(38));
+
+    let legs = fix_message.group(555).unwrap();
+    assert_eq!(Some(Ok(MonthYear { year: 2020, month: 7, day: None, week: Some(2) })), legs.instance(0).unwrap().get_month_year(610));
+    assert_eq!(Some(Ok(MonthYear { year: 2020, month: 7, day: Some(30), week: None })), legs.instance(1).unwrap().get_month_year(610));
+    assert_eq!(Some(Ok(3951)), FixMessageRef::from_tag_value(input).unwrap().get_int(34));
+}
+
+#[test]
+fn typed_getters_errors() {
+    let input = "8=FIX.4.4 | 35=8 | 38=1.5 | 14=99999999999999999999 | 54=12 | 1028=yes | 541=20200230 | 10=139";
+    let fix_message = FixMessage::from_tag_value(input).unwrap();
+    assert_eq!(Some(Err(FieldError::InvalidValue { tag: 38, value: String::from("1.5"), expected: ValueType::Int })), fix_message.get_int(38));
+    assert_eq!(Some(Err(FieldError::OutOfRange { tag: 14, value: String::from("99999999999999999999"), expected: ValueType::Int })), fix_message.get_int(14));
+    assert_eq!(Some(Ok(Decimal::new(99_999_999_999_999_999_999, 0))), fix_message.get_decimal(14));
+    assert_eq!(Some(Err(FieldError::InvalidValue { tag: 54, value: String::from("12"), expected: ValueType::Char })), fix_message.get_char(54));
+    assert_eq!(Some(Err(FieldError::InvalidValue { tag: 1028, value: String::from("yes"), expected: ValueType::Bool })), fix_message.get_bool(1028));
+    assert_eq!(Some(Err(FieldError::InvalidValue { tag: 541, value: String::from("20200230"), expected: ValueType::LocalMktDate })), fix_message.get_local_mkt_date(541));
+    assert_eq!("value of tag 38 is not a valid int: [1.5]", fix_message.get_int(38).unwrap().unwrap_err().to_string());
+
+    let fix_message = FixMessage::from_bytes(b"8=FIX.4.4 | 38=\xff | 10=139").unwrap();
+    assert_eq!(Some(Err(FieldError::InvalidUtf8 { tag: 38 })), fix_message.get_int(38));
+}
+
+#[test]
+fn utc_timestamp_precisions() {
+    let input = "8=FIX.4.4 | 52=20200520-19:15:45 | 60=20200520-19:15:45.134 | 122=20200520-19:15:45.099000 | 1132=20200520-19:15:45.000000007 | 10=139";
+    let fix_message = FixMessage::from_tag_value(input).unwrap();
+    let timestamp = |tag| fix_message.get_utc_timestamp(tag).unwrap().unwrap();
+    assert_eq!(UtcTimestamp { year: 2020, month: 5, day: 20, hour: 19, minute: 15, second: 45, nanosecond: 0, fraction_digits: 0 }, timestamp(52));
+    assert_eq!((134_000_000, 3), (timestamp(60).nanosecond, timestamp(60).fraction_digits));
+    assert_eq!((99_000_000, 6), (timestamp(122).nanosecond, timestamp(122).fraction_digits));
+    assert_eq!((7, 9), (timestamp(1132).nanosecond, timestamp(1132).fraction_digits));
+    for tag in &[52, 60, 122, 1132] {
+        assert_eq!(fix_message.get(*tag).unwrap(), timestamp(*tag).to_string());
+    }
+
+    for invalid in &["20200520-19:15:45.1", "20200520-24:00:00", "20200520 19:15:45", "20200520-19:15:45.", "20200520-19:15:45.1234567890"] {
+        assert!(invalid.parse::<UtcTimestamp>().is_err(), "{}", invalid);
+    }
+}
+
+// paths
+
+fn query<'m>(fix_message: &'m FixMessage, path: &str) -> Vec<&'m str> {
+    fix_message.query(&path.parse().unwrap())
+}
+
+#[test]
+fn query_paths() {
+    let input = "8=FIX.4.4 | 453=2 | 448=A | 448=B | 555=3 | 600=3D | 602=X | 604=2 | 605=PA | 605=2DN0 | 600=CGY | 602=Y | 604=2 | 605=PB | 605=2DQ0 | 600=3D | 602=Z | 10=209";
+    let fix_message = FixMessage::from_tag_value(input).unwrap();
+    assert_eq!(vec!["FIX.4.4"], query(&fix_message, "8"));
+    assert_eq!(vec!["A", "B"], query(&fix_message, "453[*].448"));
+    assert_eq!(vec!["A", "B"], query(&fix_message, "453.448"));
+    assert_eq!(vec!["2DQ0"], query(&fix_message, "555[1].604[1].605"));
+    assert_eq!(vec!["X", "Z"], query(&fix_message, "555[?600=3D].602"));
+    assert_eq!(vec!["PA", "2DN0"], query(&fix_message, "555[?600=3D].604.605"));
+    assert_eq!(vec!["PA", "PB"], query(&fix_message, "555[*].604[0].605"));
+    assert!(query(&fix_message, "555[3].602").is_empty());
+    assert!(query(&fix_message, "555[?600=XX].602").is_empty());
+    assert!(query(&fix_message, "604.605").is_empty());
+
+    let path: FixPath = "555[?600=3D].604[1].605".parse().unwrap();
+    assert_eq!("555[?600=3D].604[1].605", path.to_string());
+    assert_eq!(vec!["2DN0"], FixMessageRef::from_tag_value(input).unwrap().query(&path));
+    assert_eq!(vec!["2DN0"], fix_message.group(555).unwrap().instance(0).unwrap().query(&"604[1].605".parse().unwrap()));
+}
+
+#[test]
+fn invalid_paths() {
+    assert_eq!(Err(PathError::InvalidTag { offset: 0 }), "".parse::<FixPath>());
+    assert_eq!(Err(PathError::InvalidTag { offset: 4 }), "555.".parse::<FixPath>());
+    assert_eq!(Err(PathError::InvalidTag { offset: 0 }), "abc".parse::<FixPath>());
+    assert_eq!(Err(PathError::InvalidSelector { offset: 3 }), "555[1.602".parse::<FixPath>());
+    assert_eq!(Err(PathError::InvalidSelector { offset: 3 }), "555[x].602".parse::<FixPath>());
+    assert_eq!(Err(PathError::InvalidSelector { offset: 3 }), "555[?600].602".parse::<FixPath>());
+    assert_eq!(Err(PathError::InvalidTag { offset: 5 }), "555[?=3D].602".parse::<FixPath>());
+    assert_eq!(Err(PathError::UnexpectedCharacter { offset: 6 }), "555[0]602".parse::<FixPath>());
+    assert_eq!(Err(PathError::SelectorOnField { offset: 10 }), "555[0].602[1]".parse::<FixPath>());
+}
+
+// tag=value output
+
+#[test]
+fn to_tag_value_recomputes_body_length_and_checksum() {
+    let input = "8=FIX.4.4 | 9=01944 | 35=8 | 49=sender | 453=2 | 448=1 | 452=205 | 448=FIX_OUT | 452=83 | 555=2 | 600=3D | 604=2 | 605=PA | 605=2DN0 | 600=3D | 10=000";
+    let fix_message = FixMessage::from_tag_value(input).unwrap();
+    let output = fix_message.to_tag_value();
+    assert_eq!(b"8=FIX.4.4\x019=", &output[..12]);
+    assert_eq!(b'\x01', *output.last().unwrap());
+
+    let options = ParserOptions::new().verify_checksum(true).verify_body_length(true);
+    let reparsed = FixMessage::from_bytes_with(&output, &options).unwrap();
+    assert!(reparsed.warnings().is_empty(), "{:?}", reparsed.warnings());
+    assert!(reparsed.checksum().unwrap().is_valid());
+    assert!(reparsed.body_length().unwrap().is_valid());
+    assert_eq!(fix_message.query(&"555[*].604[*].605".parse().unwrap()), reparsed.query(&"555[*].604[*].605".parse().unwrap()));
+    assert_eq!(output, FixMessageRef::from_tag_value(input).unwrap().to_tag_value());
+}
+
+#[test]
+fn to_tag_value_with_separator() {
+    let input = "8=FIX.4.4 | 35=0 | 453=5 | 448=A | 448=B | 10=000";
+    let fix_message = FixMessage::from_tag_value(input).unwrap();
+    let output = String::from_utf8(fix_message.to_tag_value_with(b" | ")).unwrap();
+    assert_eq!("8=FIX.4.4 | 9=23 | 35=0 | 453=2 | 448=A | 448=B | 10=030 | ", output);
+    let output_with_soh = fix_message.to_tag_value();
+    assert_eq!(output.replace(" | ", "\x01").as_bytes(), &output_with_soh[..]);
+    assert_eq!(fix_message.to_json()["453"], FixMessage::from_tag_value(&output).unwrap().to_json()["453"]);
+
+    // without BeginString, BodyLength and CheckSum are not added
+    let fix_message = FixMessage::from_json(&serde_json::json!({"35": "0", "453": [{"448": "A"}], "58": "text"})).unwrap();
+    assert_eq!("35=0 | 453=1 | 448=A | 58=text | ", String::from_utf8(fix_message.to_tag_value_with(b" | ")).unwrap());
+}
+
+// mutation API
+
+#[test]
+fn set_insert_and_remove_fields() {
+    let input = "8=FIX.4.4 | 9=100 | 35=D | 49=sender | 11=ORDER1 | 52=20200520-19:15:45.134 | 58=text | 10=000";
+    let mut fix_message = FixMessage::from_tag_value(input).unwrap();
+    fix_message.set(11, "ORDER2");
+    fix_message.set(52, String::from("20200521-08:00:00.000"));
+    fix_message.set(1, &b"account"[..]);
+    assert!(fix_message.insert_after(49, 56, "target"));
+    assert!(!fix_message.insert_after(50, 57, "desk"));
+    assert!(fix_message.remove(58));
+    assert!(!fix_message.remove(58));
+    assert_eq!("8=FIX.4.4 | 9=70 | 35=D | 49=sender | 56=target | 11=ORDER2 | 52=20200521-08:00:00.000 | 1=account | 10=167 | ", String::from_utf8(fix_message.to_tag_value_with(b" | ")).unwrap());
+
+    let options = ParserOptions::new().verify_checksum(true).verify_body_length(true);
+    let reparsed = FixMessage::from_bytes_with(&fix_message.to_tag_value(), &options).unwrap();
+    assert!(reparsed.checksum().unwrap().is_valid() && reparsed.body_length().unwrap().is_valid());
+}
+
+#[test]
+fn push_and_remove_group_instances() {
+    let input = "8=FIX.4.4 | 35=AB | 555=2 | 600=CGY | 604=2 | 605=F7 | 605=CGYU0 | 600=CGZ | 10=000";
+    let mut fix_message = FixMessage::from_tag_value(input).unwrap();
+    {
+        let mut leg = fix_message.push_group_instance(555).unwrap();
+        leg.set(600, "CHA");
+        leg.set(602, "X");
+    }
+    assert_eq!(3, fix_message.group(555).unwrap().declared_len());
+    assert!(fix_message.group_instance_mut(555, 0).unwrap().push_group_instance(604).is_some());
+    assert!(fix_message.group_instance_mut(555, 0).unwrap().remove_group_instance(604, 0));
+    fix_message.group_instance_mut(555, 0).unwrap().group_instance_mut(604, 1).unwrap().set(605, "F8");
+    assert!(fix_message.remove_group_instance(555, 1));
+    assert!(!fix_message.remove_group_instance(555, 2));
+    assert!(fix_message.push_group_instance(453).is_none());
+    assert!(!fix_message.set(555, "3"));
+    assert_eq!(2, fix_message.group(555).unwrap().declared_len());
+    assert_eq!(r#"{"8":"FIX.4.4","35":"AB","555":[{"600":"CGY","604":[{"605":"CGYU0"},{"605":"F8"}]},{"600":"CHA","602":"X"}],"10":"000"}"#, fix_message.to_json().to_string());
+
+    assert!(fix_message.remove_group_instance(555, 0));
+    assert!(fix_message.remove_group_instance(555, 0));
+    assert_eq!(0, fix_message.group(555).unwrap().declared_len());
+    assert_eq!("8=FIX.4.4 | 9=6 | 35=AB | 10=247 | ", String::from_utf8(fix_message.to_tag_value_with(b" | ")).unwrap());
+    assert!(fix_message.remove(555));
+    assert!(fix_message.group(555).is_none());
+}
+
+#[test]
+fn push_group_instances_without_fields() {
+    // an instance is counted even if none of its fields is set
+    let mut fix_message = FixMessage::from_tag_value("8=FIX.4.4 | 35=D | 453=2 | 448=A | 448=B | 10=000").unwrap();
+    assert!(fix_message.push_group_instance(453).is_some());
+    assert_eq!(3, fix_message.group(453).unwrap().declared_len());
+    assert_eq!("8=FIX.4.4 | 9=23 | 35=D | 453=3 | 448=A | 448=B | 10=051 | ", String::from_utf8(fix_message.to_tag_value_with(b" | ")).unwrap());
+
+    // without a dictionary, a group with a single instance is parsed as plain fields
+    let input = "8=FIX.4.4 | 35=D | 453=1 | 448=A | 10=000";
+    assert!(FixMessage::from_tag_value(input).unwrap().push_group_instance(453).is_none());
+    #[cfg(feature = "dictionary")]
+    {
+        let mut fix_message = FixMessage::from_tag_value_with_dictionary(input, &fix44_dictionary()).unwrap();
+        fix_message.push_group_instance(453).unwrap().set(448, "B");
+        assert_eq!(vec!["A", "B"], fix_message.get_all(448));
+    }
+}
+
+// builder
+
+#[test]
+fn build_message() {
+    let fix_message = FixMessageBuilder::new("FIX.4.4", "8")
+        .field(11, "ORDER1")
+        .sending_time("20200520-19:15:45.134")
+        .msg_seq_num(3951)
+        .target_comp_id("target")
+        .sender_comp_id("sender")
+        .sender_comp_id("other")
+        .group(453, |parties| parties.instance(|party| party.field(448, "1").field(452, "205")).instance(|party| party.field(448, "FIX_OUT").field(452, "83")))
+        .group(555, |legs| legs.instance(|leg| leg.field(600, "3D").group(604, |ids| ids.instance(|id| id.field(605, "PA")).instance(|id| id.field(605, "2DN0")))).instance(|leg| leg.field(600, "CGY")))
+        .build();
+
+    let output = r#"{"8":"FIX.4.4","9":"149","35":"8","49":"other","56":"target","34":"3951","52":"20200520-19:15:45.134","11":"ORDER1","453":[{"448":"1","452":"205"},{"448":"FIX_OUT","452":"83"}],"555":[{"600":"3D","604":[{"605":"PA"},{"605":"2DN0"}]},{"600":"CGY"}],"10":"226"}"#;
+    assert_eq!(output, fix_message.to_json().to_string());
+    assert_eq!(2, fix_message.group(555).unwrap().declared_len());
+    assert_eq!(605, fix_message.group(555).unwrap().instance(0).unwrap().group(604).unwrap().delimiter());
+
+    let options = ParserOptions::new().verify_checksum(true).verify_body_length(true);
+    let reparsed = FixMessage::from_bytes_with(&fix_message.to_tag_value(), &options).unwrap();
+    assert!(reparsed.warnings().is_empty(), "{:?}", reparsed.warnings());
+    assert_eq!(output, reparsed.to_json().to_string());
+}
+
+#[test]
+fn build_message_with_header_tags_as_fields() {
+    // 9 and 10 are computed, the header tags are set in the header
+    let fix_message = FixMessageBuilder::new("FIX.4.4", "D").field(9, "99").field(11, "order").field(10, "000").field(49, "sender").field(35, "F").build();
+    assert_eq!(r#"{"8":"FIX.4.4","9":"24","35":"F","49":"sender","11":"order","10":"210"}"#, fix_message.to_json().to_string());
+    let options = ParserOptions::new().verify_checksum(true).verify_body_length(true);
+    assert!(FixMessage::from_bytes_with(&fix_message.to_tag_value(), &options).unwrap().warnings().is_empty());
+}
+
+// json input
+
+#[test]
+fn from_json_round_trip() {
+    let input = "8=FIX.4.4 | 9=01944 | 35=8 | 49=sender | 453=2 | 448=1 | 452=205 | 448=FIX_OUT | 452=83 | 555=2 | 600=3D | 604=2 | 605=PA | 605=2DN0 | 600=3D | 10=000";
+    let fix_message = FixMessage::from_tag_value(input).unwrap();
+    let from_json = FixMessage::from_json(&fix_message.to_json()).unwrap();
+    assert_eq!(fix_message.to_json(), from_json.to_json());
+    assert_eq!(fix_message.to_tag_value(), from_json.to_tag_value());
+    assert_eq!(2, from_json.group(555).unwrap().declared_len());
+}
+
+#[test]
+fn from_json_keeps_key_order() {
+    let json: serde_json::Value = serde_json::from_str(r#"{"8":"FIX.4.4","35":"0","58":"b","1":"a","453":[{"448":"A","452":3},{"448":"B"}],"10":"000"}"#).unwrap();
+    let fix_message = FixMessage::from_json(&json).unwrap();
+    assert_eq!("8=FIX.4.4|9=38|35=0|58=b|1=a|453=2|448=A|452=3|448=B|10=013|", String::from_utf8(fix_message.to_tag_value_with(b"|")).unwrap());
+    assert_eq!(fixparser::from_json_to_tag_value(&json.to_string()).as_bytes(), &fix_message.to_tag_value()[..]);
+}
+
+#[test]
+fn from_json_errors() {
+    let from_json = |json: &str| FixMessage::from_json(&serde_json::from_str(json).unwrap()).err();
+    assert_eq!(Some(JsonError::NotAnObject), from_json(r#"["8", "FIX.4.4"]"#));
+    assert_eq!(Some(JsonError::InvalidTag { key: String::from("abc") }), from_json(r#"{"8":"FIX.4.4","abc":"1"}"#));
+    assert_eq!(Some(JsonError::InvalidValue { tag: 58 }), from_json(r#"{"8":"FIX.4.4","58":null}"#));
+    assert_eq!(Some(JsonError::InvalidGroupInstance { tag: 453, index: 1 }), from_json(r#"{"8":"FIX.4.4","453":[{"448":"A"},"B"]}"#));
+    assert_eq!(r#"{"error":"invalid tag [abc]"}"#, fixparser::from_json_to_tag_value(r#"{"abc":"1"}"#));
+    assert!(fixparser::from_json_to_tag_value("{").starts_with(r#"{"error":"#));
+}
+
+// serde
+
+#[test]
+fn serialize_message() {
+    let input = "8=FIX.4.4 | 555=2 | 600=CGY | 604=2 | 605=F7 | 605=CGYU0 | 600=CGY | 10=209";
+    let fix_message = FixMessage::from_tag_value(input).unwrap();
+    assert_eq!(fix_message.to_json().to_string(), serde_json::to_string(&fix_message).unwrap());
+    assert_eq!(fix_message.to_json().to_string(), serde_json::to_string(&FixMessageRef::from_tag_value(input).unwrap()).unwrap());
+}
+
+#[derive(Debug, PartialEq, serde::Deserialize)]
+enum Side {
+    #[serde(rename = "1")]
+    Buy,
+    #[serde(rename = "2")]
+    Sell,
+}
+
+#[derive(Debug, PartialEq, serde::Deserialize)]
+struct Party {
+    #[serde(rename = "448")]
+    party_id: String,
+    #[serde(rename = "452")]
+    party_role: u16,
+}
+
+#[derive(Debug, PartialEq, serde::Deserialize)]
+struct NewOrder<'a> {
+    #[serde(rename = "35")]
+    msg_type: char,
+    #[serde(rename = "11")]
+    cl_ord_id: &'a str,
+    #[serde(rename = "54")]
+    side: Side,
+    #[serde(rename = "38")]
+    order_qty: u64,
+    #[serde(rename = "44")]
+    price: Option<f64>,
+    #[serde(rename = "99")]
+    stop_px: Option<f64>,
+    #[serde(rename = "1028")]
+    manual_order_indicator: bool,
+    #[serde(rename = "453")]
+    parties: Vec<Party>,
+}
+
+#[test]
+fn deserialize_into_struct() {
+    let input = "8=FIX.4.4 | 35=D | 11=ORDER1 | 54=2 | 38=10 | 44=2900.25 | 1028=Y | 453=2 | 448=A | 452=1 | 448=B | 452=3 | 10=000";
+    let fix_message = FixMessage::from_tag_value(input).unwrap();
+    let expected = NewOrder { msg_type: 'D', cl_ord_id: "ORDER1", side: Side::Sell, order_qty: 10, price: Some(2900.25), stop_px: None, manual_order_indicator: true, parties: vec![Party { party_id: String::from("A"), party_role: 1 }, Party { party_id: String::from("B"), party_role: 3 }] };
+    assert_eq!(expected, fix_message.deserialize_into::<NewOrder>().unwrap());
+    assert_eq!(expected, FixMessageRef::from_tag_value(input).unwrap().deserialize_into::<NewOrder>().unwrap());
+    assert_eq!(Party { party_id: String::from("B"), party_role: 3 }, fix_message.group(453).unwrap().instance(1).unwrap().deserialize_into::<Party>().unwrap());
+
+    let tags: std::collections::BTreeMap<i32, serde_json::Value> = fix_message.deserialize_into().unwrap();
+    assert_eq!(Some(&serde_json::json!("ORDER1")), tags.get(&11));
+
+    // groups with a single instance are not detected without a dictionary, but their instances are the fields of the struct which follow the NoXXX field
+    let input = "8=FIX.4.4 | 35=D | 11=ORDER1 | 38=10 | 1028=N | 453=1 | 448=X | 452=1 | 54=1 | 10=000";
+    let new_order = FixMessage::from_tag_value(input).unwrap().deserialize_into::<NewOrder>().map(|new_order| (new_order.side, new_order.parties));
+    assert_eq!((Side::Buy, vec![Party { party_id: String::from("X"), party_role: 1 }]), new_order.unwrap());
+    let input = "8=FIX.4.4 | 35=D | 11=ORDER1 | 38=10 | 1028=N | 453=2 | 448=X | 452=1 | 54=1 | 10=000";
+    assert_eq!(1, FixMessageRef::from_tag_value(input).unwrap().deserialize_into::<NewOrder>().unwrap().parties.len());
+    let input = "8=FIX.4.4 | 35=D | 11=ORDER1 | 54=1 | 38=10 | 1028=N | 453=0 | 10=000";
+    assert!(FixMessage::from_tag_value(input).unwrap().deserialize_into::<NewOrder>().unwrap().parties.is_empty());
+}
+
+#[test]
+fn deserialize_into_struct_errors() {
+    let input = "8=FIX.4.4 | 35=D | 11=ORDER1 | 54=2 | 38=ten | 1028=Y | 453=2 | 448=A | 452=1 | 448=B | 452=3 | 10=000";
+    let fix_message = FixMessage::from_tag_value(input).unwrap();
+    assert_eq!("invalid value for tag 38 (expected an unsigned int): [ten]", fix_message.deserialize_into::<NewOrder>().unwrap_err().to_string());
+    let fix_message = FixMessage::from_tag_value("8=FIX.4.4 | 35=D | 11=ORDER1 | 10=000").unwrap();
+    assert_eq!("missing field `54`", fix_message.deserialize_into::<NewOrder>().unwrap_err().to_string());
+}
+
+// dictionaries
+
+#[cfg(feature = "dictionary")]
+fn fix44_dictionary() -> Dictionary {
+    Dictionary::from_quickfix_file(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/dictionaries/FIX44.xml")).unwrap()
+}
+
+#[cfg(feature = "dictionary")]
+#[test]
+fn load_quickfix_dictionary() {
+    let dictionary = fix44_dictionary();
+    assert_eq!("FIX.4.4", dictionary.version());
+    assert_eq!(44, dictionary.fields().count());
+    assert_eq!(Some(54), dictionary.field_by_name("Side").map(|field| field.tag));
+    let side = dictionary.field(54).unwrap();
+    assert_eq!(("Side", "CHAR", Some("SELL")), (side.name.as_str(), side.field_type.as_str(), side.value_description("2")));
+    assert_eq!(None, side.value_description("3"));
+    assert_eq!(vec![8, 9, 35, 49, 56, 34, 52, 627], dictionary.header().members.iter().map(|member| match member { Member::Field { tag, .. } => *tag, Member::Group(group) => group.no_tag, Member::Component { .. } => 0 }).collect::<Vec<_>>());
+
+    let new_order = dictionary.message("D").unwrap();
+    assert_eq!(("NewOrderSingle", "app"), (new_order.name.as_str(), new_order.category.as_str()));
+    assert_eq!(Some(&Member::Field { tag: 11, required: true }), new_order.members.first());
+    assert!(!new_order.members[1].is_required() && new_order.members[3].is_required());
+    let parties = new_order.group(453).unwrap();
+    assert_eq!(("NoPartyIDs", 448), (parties.name.as_str(), parties.delimiter));
+    assert_eq!(vec![448, 447, 452, 802], parties.member_tags());
+    assert_eq!(vec![523, 803], parties.group(802).unwrap().member_tags());
+    assert_eq!(455, new_order.group(454).unwrap().delimiter);
+    assert!(new_order.group(802).is_none());
+
+    let legs = dictionary.message("AB").unwrap().group(555).unwrap();
+    assert_eq!((600, vec![600, 604, 611, 687]), (legs.delimiter, legs.member_tags()));
+    assert_eq!(vec!["Instrument", "InstrumentLeg", "Parties", "PtysSubGrp"], dictionary.components().map(|component| component.name.as_str()).collect::<Vec<_>>());
+    assert_eq!(Some(627), dictionary.header().group(627).map(|group| group.no_tag));
+}
+
+#[cfg(feature = "dictionary")]
+#[test]
+fn invalid_quickfix_dictionaries() {
+    let dictionary = |members: &str| Dictionary::from_quickfix_xml(&format!(r#"<fix major="4" minor="2"><messages><message name="M" msgtype="U1">{}</message></messages><components><component name="C"><field name="Account"/></component></components><fields><field number="1" name="Account" type="STRING"/></fields></fix>"#, members));
+    assert_eq!("FIX.4.2", dictionary("").unwrap().version());
+    assert_eq!(Err(DictionaryError::UnknownField { name: String::from("Side") }), dictionary(r#"<field name="Side"/>"#));
+    assert_eq!(Err(DictionaryError::UnknownComponent { name: String::from("Other") }), dictionary(r#"<component name="Other"/>"#));
+    assert_eq!(Some(&Member::Component { name: String::from("C"), required: true, members: vec![Member::Field { tag: 1, required: false }] }), dictionary(r#"<component name="C" required="Y"/>"#).unwrap().message("U1").unwrap().members.first());
+    assert_eq!(Err(DictionaryError::RecursiveComponent { name: String::from("R") }), Dictionary::from_quickfix_xml(r#"<fix major="4" minor="2"><components><component name="R"><component name="R"/></component></components></fix>"#));
+    assert_eq!(Err(DictionaryError::EmptyGroup { name: String::from("Account") }), dictionary(r#"<group name="Account"></group>"#));
+    assert_eq!(Err(DictionaryError::MissingAttribute { element: String::from("field"), attribute: String::from("name") }), dictionary(r#"<field/>"#));
+    assert!(matches!(Dictionary::from_quickfix_xml("<fix>"), Err(DictionaryError::Xml { .. })));
+    assert!(matches!(Dictionary::from_quickfix_file("does-not-exist.xml"), Err(DictionaryError::Io { .. })));
+}
+
+#[cfg(feature = "dictionary")]
+#[test]
+fn load_orchestra_dictionary() {
+    let dictionary = Dictionary::from_orchestra_file(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/dictionaries/OrchestraFIX44.xml")).unwrap();
+    let quickfix_dictionary = fix44_dictionary();
+    assert_eq!("FIX.4.4", dictionary.version());
+    assert_eq!(quickfix_dictionary.fields().map(|field| (field.tag, &field.name)).collect::<Vec<_>>(), dictionary.fields().map(|field| (field.tag, &field.name)).collect::<Vec<_>>());
+    let side = dictionary.field_by_name("Side").unwrap();
+    assert_eq!(("char", Some("Sell")), (side.field_type.as_str(), side.value_description("2")));
+    assert_eq!("String", dictionary.field(11).unwrap().field_type);
+
+    // same structure as the QuickFIX dictionary (the scenarios other than the base one are ignored)
+    assert_eq!(quickfix_dictionary.header().members, dictionary.header().members);
+    assert_eq!(quickfix_dictionary.trailer().members, dictionary.trailer().members);
+    assert_eq!(quickfix_dictionary.components().collect::<Vec<_>>(), dictionary.components().collect::<Vec<_>>());
+    for message in quickfix_dictionary.messages() {
+        let orchestra_message = dictionary.message(&message.msg_type).unwrap();
+        assert_eq!((&message.name, &message.members), (&orchestra_message.name, &orchestra_message.members));
+    }
+    assert_eq!(("SingleGeneralOrderHandling", 448), (dictionary.message("D").unwrap().category.as_str(), dictionary.message("D").unwrap().group(453).unwrap().delimiter));
+
+    // it can be used to parse the messages
+    let input = "8=FIX.4.4 | 35=U1 | 1000=2 | 1001=1 | 1002=2 | 1001=10 | 1002=20 | 1003=30 | 10=209";
+    assert_eq!(FixMessage::from_tag_value_with_dictionary(input, &quickfix_dictionary).unwrap().to_json(), FixMessage::from_tag_value_with_dictionary(input, &dictionary).unwrap().to_json());
+}
+
+#[cfg(feature = "dictionary")]
+#[test]
+fn invalid_orchestra_dictionaries() {
+    let dictionary = |members: &str| Dictionary::from_orchestra_xml(&format!(r#"<fixr:repository xmlns:fixr="http://fixprotocol.io/2020/orchestra/repository" name="FIX.4.2"><fixr:fields><fixr:field id="1" name="Account" type="String"/><fixr:field id="73" name="NoOrders" type="NumInGroup"/></fixr:fields><fixr:groups><fixr:group id="2000" name="EmptyGrp"><fixr:numInGroup id="73"/></fixr:group></fixr:groups><fixr:messages><fixr:message name="M" msgType="U1"><fixr:structure>{}</fixr:structure></fixr:message></fixr:messages></fixr:repository>"#, members));
+    assert_eq!("FIX.4.2", dictionary("").unwrap().version());
+    assert_eq!(Err(DictionaryError::UnknownField { name: String::from("54") }), dictionary(r#"<fixr:fieldRef id="54"/>"#));
+    assert_eq!(Err(DictionaryError::UnknownComponent { name: String::from("1001") }), dictionary(r#"<fixr:componentRef id="1001"/>"#));
+    assert_eq!(Err(DictionaryError::UnknownGroup { name: String::from("2001") }), dictionary(r#"<fixr:groupRef id="2001"/>"#));
+    assert_eq!(Err(DictionaryError::EmptyGroup { name: String::from("NoOrders") }), dictionary(r#"<fixr:groupRef id="2000"/>"#));
+    assert_eq!(Err(DictionaryError::InvalidTag { value: String::from("X") }), dictionary(r#"<fixr:fieldRef id="X"/>"#));
+    assert_eq!(Err(DictionaryError::RecursiveComponent { name: String::from("R") }), Dictionary::from_orchestra_xml(r#"<repository name="FIX.4.2"><components><component id="1" name="R"><componentRef id="1"/></component></components></repository>"#));
+    assert!(matches!(Dictionary::from_orchestra_xml("<fix/>"), Err(DictionaryError::Xml { .. })));
+}
+
+#[cfg(feature = "dictionary")]
+#[test]
+fn groups_defined_in_dictionary() {
+    let dictionary = fix44_dictionary();
+    let parse = |input: &str| FixMessage::from_tag_value_with_dictionary(input, &dictionary).unwrap().to_json().to_string();
+
+    // the last field is a member of the group according to the dictionary
+    let input = "8=FIX.4.4 | 35=U1 | 1000=2 | 1001=1 | 1002=2 | 1001=10 | 1002=20 | 1003=30 | 10=209";
+    assert_eq!(r#"{"8":"FIX.4.4","35":"U1","1000":[{"1001":"1","1002":"2"},{"1001":"10","1002":"20"}],"1003":"30","10":"209"}"#, FixMessage::from_tag_value(input).unwrap().to_json().to_string());
+    assert_eq!(r#"{"8":"FIX.4.4","35":"U1","1000":[{"1001":"1","1002":"2"},{"1001":"10","1002":"20","1003":"30"}],"10":"209"}"#, parse(input));
+
+    // groups with a single instance are detected
+    assert_eq!(r#"{"8":"FIX.4.4","35":"D","11":"ID","453":[{"448":"A","452":"3"}],"54":"1","10":"209"}"#, parse("8=FIX.4.4 | 35=D | 11=ID | 453=1 | 448=A | 452=3 | 54=1 | 10=209"));
+    assert_eq!(r#"{"8":"FIX.4.4","627":[{"628":"HOP"}],"35":"D","10":"209"}"#, parse("8=FIX.4.4 | 627=1 | 628=HOP | 35=D | 10=209"));
+
+    // nested groups and fields of the components
+    assert_eq!(r#"{"8":"FIX.4.4","35":"D","453":[{"448":"A","802":[{"523":"S"}]},{"448":"B"}],"55":"EUR","454":[{"455":"X"}],"10":"209"}"#, parse("8=FIX.4.4 | 35=D | 453=2 | 448=A | 802=1 | 523=S | 448=B | 55=EUR | 454=1 | 455=X | 10=209"));
+
+    // a defined tag which is not a member ends the group, even if there are instances missing
+    assert_eq!(r#"{"8":"FIX.4.4","35":"D","453":[{"448":"A"},{"448":"B"}],"54":"1","10":"209"}"#, parse("8=FIX.4.4 | 35=D | 453=3 | 448=A | 448=B | 54=1 | 10=209"));
+
+    // custom tags (unknown to the dictionary) are parsed as usual, including custom groups
+    assert_eq!(r#"{"8":"FIX.4.4","35":"AB","555":[{"600":"A","9000":[{"9001":"a"},{"9001":"b"}],"687":"1","9002":"X"},{"600":"B","9002":"Y"}],"54":"1","10":"209"}"#, parse("8=FIX.4.4 | 35=AB | 555=2 | 600=A | 9000=2 | 9001=a | 9001=b | 687=1 | 9002=X | 600=B | 9002=Y | 54=1 | 10=209"));
+
+    // a NoXXX field which is not a number opens a group without repetitions
+    let message = FixMessage::from_tag_value_with_dictionary("8=FIX.4.4 | 35=D | 453=X | 448=A | 448=B | 54=1 | 10=209", &dictionary).unwrap();
+    assert_eq!(r#"{"8":"FIX.4.4","35":"D","453":[{"448":"A"},{"448":"B"}],"54":"1","10":"209"}"#, message.to_json().to_string());
+    assert_eq!(vec![(WarningKind::InvalidGroupCount { value: String::from("X") }, Some(453), 27), (WarningKind::DuplicateTag, Some(448), 27), (WarningKind::DuplicateTag, Some(448), 35)], warnings_of(&message));
+
+    // options are applied as without a dictionary
+    let input = "8=FIX.4.4 | 35=D | 453=1 | 448=A | 448=B | 10=209";
+    let error = FixMessage::from_tag_value_with_dictionary_with(input, &dictionary, &ParserOptions::strict()).err();
+    assert!(matches!(error, Some(ParseError::Rejected { ref warning }) if warning.kind == WarningKind::MisplacedHeaderTag { expected: 9 }));
+    let options = ParserOptions::new().reject_duplicate_tags(true);
+    assert!(matches!(FixMessage::from_tag_value_with_dictionary_with(input, &dictionary, &options), Err(ParseError::Rejected { ref warning }) if warning.tag == Some(448)));
+
+    // messages not defined in the dictionary are parsed as usual
+    let input = "8=FIX.4.4 | 35=U9 | 453=2 | 448=A | 448=B | 1003=30 | 10=209";
+    assert_eq!(FixMessage::from_tag_value(input).unwrap().to_json().to_string(), parse(input));
+}
+
+#[cfg(feature = "dictionary")]
+#[test]
+fn json_with_names() {
+    let dictionary = fix44_dictionary();
+    let input = "8=FIX.4.4 | 35=D | 453=2 | 448=A | 452=3 | 448=B | 54=1 | 9999=X | 10=209";
+    let fix_message = FixMessage::from_tag_value(input).unwrap();
+    assert_eq!(r#"{"BeginString":"FIX.4.4","MsgType":"D","NoPartyIDs":[{"PartyID":"A","PartyRole":"3"},{"PartyID":"B"}],"Side":"1","9999":"X","CheckSum":"209"}"#, fix_message.to_json_with_names(&dictionary).to_string());
+    assert_eq!(fix_message.to_json_with_names(&dictionary), FixMessageRef::from_tag_value(input).unwrap().to_json_with_names(&dictionary));
+
+    let annotated = fix_message.to_annotated_json(&dictionary);
+    assert_eq!(r#"{"tag":54,"name":"Side","value":"1","enum":"BUY"}"#, annotated["Side"].to_string());
+    assert_eq!(r#"{"tag":448,"name":"PartyID","value":"A"}"#, annotated["NoPartyIDs"][0]["PartyID"].to_string());
+    assert_eq!(r#"{"tag":452,"name":"PartyRole","value":"3","enum":"CLIENT_ID"}"#, annotated["NoPartyIDs"][0]["PartyRole"].to_string());
+    assert_eq!(r#"{"tag":9999,"value":"X"}"#, annotated["9999"].to_string());
+    assert_eq!(fix_message.to_annotated_json(&dictionary), FixMessageRef::from_tag_value(input).unwrap().to_annotated_json(&dictionary));
+}
+
+#[cfg(feature = "dictionary")]
+#[test]
+fn json_with_names_from_wasm() {
+    let dictionary_xml = std::fs::read_to_string(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/dictionaries/FIX44.xml")).unwrap();
+    let input = "8=FIX.4.4 | 35=D | 453=1 | 448=A | 54=2 | 10=209";
+    assert_eq!(r#"{"BeginString":"FIX.4.4","MsgType":"D","NoPartyIDs":[{"PartyID":"A"}],"Side":"2","CheckSum":"209"}"#, fixparser::from_tag_value_to_named_json(input, &dictionary_xml, false));
+    assert!(fixparser::from_tag_value_to_named_json(input, &dictionary_xml, true).contains(r#""Side":{"tag":54,"name":"Side","value":"2","enum":"SELL"}"#));
+
+    let orchestra_xml = std::fs::read_to_string(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/dictionaries/OrchestraFIX44.xml")).unwrap();
+    assert!(fixparser::from_tag_value_to_named_json(input, &orchestra_xml, true).contains(r#""Side":{"tag":54,"name":"Side","value":"2","enum":"Sell"}"#));
+    assert_eq!(r#"{"error":"invalid dictionary: the root element is not <repository>"}"#, fixparser::from_tag_value_to_named_json(input, "<dictionary/>", false));
+    assert_eq!(r#"{"error":"unknown field [Side]"}"#, fixparser::from_tag_value_to_named_json(input, r#"<fix><header><field name="Side"/></header></fix>"#, false));
+}
+
+#[cfg(feature = "dictionary")]
+#[test]
+fn validate_against_dictionary() {
+    let dictionary = fix44_dictionary();
+    let validate = |input: &str| FixMessage::from_tag_value_with_dictionary(input, &dictionary).unwrap().validate(&dictionary).into_iter().map(|issue| (issue.kind, issue.tag, issue.path.to_string(), issue.value)).collect::<Vec<_>>();
+
+    let header = "8=FIX.4.4 | 9=100 | 35=D | 49=SENDER | 56=TARGET | 34=1 | 52=20200520-19:15:45.134";
+    assert_eq!(Vec::<(IssueKind, i32, String, String)>::new(), validate(&format!("{} | 11=ID | 55=EUR | 54=1 | 60=20200520-19:15:45 | 40=2 | 44=1.5 | 10=209", header)));
+    assert_eq!(vec![
+        (IssueKind::InvalidValue { expected: ValueType::Int }, 452, String::from("453[0].452"), String::from("X")),
+        (IssueKind::ValueNotInEnum, 452, String::from("453[1].452"), String::from("7")),
+        (IssueKind::InvalidValue { expected: ValueType::Decimal }, 44, String::from("44"), String::from("1,5")),
+        (IssueKind::ValueNotInEnum, 54, String::from("54"), String::from("9")),
+        (IssueKind::UndefinedTag, 555, String::from("555"), String::from("1")),
+        (IssueKind::UndefinedTag, 9999, String::from("9999"), String::from("X")),
+        (IssueKind::InvalidValue { expected: ValueType::UtcTimestamp }, 60, String::from("60"), String::from("2020-05-20")),
+        (IssueKind::MissingRequiredField, 11, String::from("11"), String::new()),
+        (IssueKind::MissingRequiredField, 40, String::from("40"), String::new()),
+    ], validate(&format!("{} | 453=2 | 448=A | 452=X | 448=B | 452=7 | 55=EUR | 44=1,5 | 54=9 | 555=1 | 9999=X | 60=2020-05-20 | 10=209", header)));
+
+    // the required fields of an optional component are required only if the component is present
+    assert_eq!(vec![(IssueKind::MissingRequiredField, 34, String::from("34"), String::new())], validate("8=FIX.4.4 | 9=5 | 35=0 | 49=SENDER | 56=TARGET | 52=20200520-19:15:45 | 10=209"));
+
+    // groups whose count doesn't match their instances (with and without the dictionary)
+    let input = format!("{} | 11=ID | 55=EUR | 54=1 | 60=20200520-19:15:45 | 40=2 | 453=3 | 448=A | 448=B | 10=209", header);
+    assert_eq!(vec![(IssueKind::GroupCountMismatch { declared: 3, instances: 2 }, 453, String::from("453"), String::from("3"))], validate(&input));
+    let input = "8=FIX.4.4 | 35=AB | 555=3 | 600=QWE | 600=RTY | 10=209";
+    let issues = FixMessage::from_tag_value(input).unwrap().validate(&dictionary);
+    assert_eq!(Some(&IssueKind::GroupCountMismatch { declared: 3, instances: 2 }), issues.iter().map(|issue| &issue.kind).find(|kind| matches!(kind, IssueKind::GroupCountMismatch { .. })));
+    let input = format!("{} | 11=ID | 55=EUR | 54=1 | 60=20200520-19:15:45 | 40=2 | 453=1 | 448=A | 10=209", header);
+    assert_eq!(vec![(IssueKind::GroupCountMismatch { declared: 1, instances: 0 }, 453, String::from("453"), String::from("1")), (IssueKind::UndefinedTag, 448, String::from("448"), String::from("A"))], FixMessage::from_tag_value(&input).unwrap().validate(&dictionary).into_iter().map(|issue| (issue.kind, issue.tag, issue.path.to_string(), issue.value)).collect::<Vec<_>>());
+
+    // tags in groups not defined in the dictionary are not validated
+    assert!(validate(&format!("{} | 11=ID | 55=EUR | 54=1 | 60=20200520-19:15:45 | 40=2 | 9000=2 | 9001=a | 9001=b | 10=209", header)).iter().all(|(_, tag, _, _)| *tag == 9000));
+
+    let issues = FixMessage::from_tag_value("8=FIX.4.4 | 35=ZZ | 10=209").unwrap().validate(&dictionary);
+    assert_eq!(vec![ValidationIssue { kind: IssueKind::UnknownMsgType, tag: 35, path: "35".parse().unwrap(), value: String::from("ZZ") }], issues);
+    assert_eq!("Message type [ZZ] is not defined", issues[0].to_string());
+    assert_eq!(FixMessageRef::from_tag_value(&input).unwrap().validate(&dictionary), FixMessage::from_tag_value(&input).unwrap().validate(&dictionary));
+}
+
+// streams
+
+// reader which returns at most the given number of bytes on each read, and fails after the given number of reads
+struct ChunkedReader<'a> {
+    input: &'a [u8],
+    chunk_size: usize,
+    reads_before_error: Option<usize>,
+}
+
+impl std::io::Read for ChunkedReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if let Some(reads) = self.reads_before_error.as_mut() {
+            if *reads == 0 {
+                return Err(std::io::Error::other("connection reset"));
+            }
+            *reads -= 1;
+        }
+        let length = self.chunk_size.min(buf.len()).min(self.input.len());
+        buf[..length].copy_from_slice(&self.input[..length]);
+        self.input = &self.input[length..];
+        Ok(length)
+    }
+}
+
+#[test]
+fn stream_of_messages() {
+    // the message after the first one is not dropped as in FixMessage::from_tag_value
+    let input = "8=FIX.4.4 | 10=209 | 11=some | 8=FIX.4.4 | 35=0 | 10=209";
+    let messages = FixStreamParser::new(input.as_bytes()).map(|message| message.unwrap().to_json().to_string()).collect::<Vec<_>>();
+    assert_eq!(vec![r#"{"8":"FIX.4.4","10":"209"}"#, r#"{"8":"FIX.4.4","35":"0","10":"209"}"#], messages);
+
+    // concatenated messages (with and without garbage between them) split in arbitrary reads
+    let mut stream = Vec::new();
+    let mut expected = Vec::new();
+    let mut offsets = Vec::new();
+    for seq_num in 1..=50 {
+        let fix_message = FixMessageBuilder::new("FIX.4.4", "D").msg_seq_num(seq_num).field(58, "58=FIX.4.4 is not a begin string").group(453, |parties| parties.instance(|party| party.field(448, "A")).instance(|party| party.field(448, "B"))).build();
+        offsets.push(stream.len() as u64);
+        stream.extend(fix_message.to_tag_value());
+        if seq_num % 3 == 0 {
+            stream.extend(b"\n<garbage 8=FI 8=FIX. 10=>\n");
+        }
+        expected.push(fix_message.to_json());
+    }
+    for chunk_size in [3, 100, 100_000] {
+        let reader = ChunkedReader { input: &stream, chunk_size, reads_before_error: None };
+        let messages = FixStreamParser::new(reader).map(|message| message.unwrap()).collect::<Vec<_>>();
+        assert!(messages.iter().all(|message| message.warnings().is_empty()));
+        assert_eq!(expected, messages.iter().map(FixMessage::to_json).collect::<Vec<_>>());
+    }
+    let mut messages = FixStreamParser::new(std::io::BufReader::new(&stream[..]));
+    for offset in offsets {
+        messages.next().unwrap().unwrap();
+        assert_eq!(offset, messages.offset());
+    }
+}
+
+#[test]
+fn stream_of_messages_with_data_fields() {
+    // the value of the data field contains the separator and a checksum field, but the end of the message is found with its body length
+    let fix_message = FixMessageBuilder::new("FIX.4.4", "D").field(95, "7").field(96, "a\x0110=12").build();
+    let mut stream = fix_message.to_tag_value();
+    stream.extend(fix_message.to_tag_value());
+    let messages = FixStreamParser::new(&stream[..]).map(|message| message.unwrap()).collect::<Vec<_>>();
+    assert_eq!(2, messages.len());
+    assert_eq!(Some("a\x0110=12"), messages[1].get(96));
+    assert_eq!(Some(&stream[..stream.len() / 2]), Some(&messages[0].to_tag_value()[..]));
+
+    // a body length too big to be real is ignored, and the checksum is looked for
+    let stream = b"8=FIX.4.4\x019=18446744073709551615\x0135=0\x0110=000\x018=FIX.4.4\x019=99999999999\x0135=1\x0110=000\x01";
+    let messages = FixStreamParser::new(&stream[..]).map(|message| message.unwrap()).collect::<Vec<_>>();
+    assert_eq!(vec![Some("0"), Some("1")], messages.iter().map(|message| message.get(35)).collect::<Vec<_>>());
+}
+
+#[test]
+fn stream_of_messages_without_checksum() {
+    let input = "8=FIX.4.4 | 35=0 | 8=FIX.4.4 | 35=1 | 10=209 | 8=FIX.4.4 | 35=2";
+    let messages = FixStreamParser::new(input.as_bytes()).map(|message| message.unwrap()).collect::<Vec<_>>();
+    assert_eq!(vec![Some("0"), Some("1"), Some("2")], messages.iter().map(|message| message.get(35)).collect::<Vec<_>>());
+    assert_eq!(vec![true, false, true], messages.iter().map(|message| message.warnings().iter().any(|warning| warning.kind == WarningKind::MissingChecksum)).collect::<Vec<_>>());
+
+    // invalid messages are errors, and the parsing goes on with the next one
+    let input = "8=FIX.4.4 | 35=0 | 10=209 | 8=FIX.4.4 | 1=2 | 453=X | 448=A | 448=B | 10=209 | 8=FIX.4.4 | 35=2";
+    let messages = FixStreamParser::with_options(input.as_bytes(), ParserOptions::new().reject_duplicate_tags(true)).collect::<Vec<_>>();
+    assert_eq!(3, messages.len());
+    assert!(matches!(messages[1], Err(ParseError::Rejected { ref warning }) if warning.tag == Some(448)));
+    assert_eq!(Some("2"), messages[2].as_ref().unwrap().get(35));
+}
+
+#[test]
+fn stream_read_error() {
+    let input = "8=FIX.4.4 | 35=0 | 10=209 | 8=FIX.4.4 | 35=1 | 10=209 | ";
+    let reader = ChunkedReader { input: input.as_bytes(), chunk_size: 40, reads_before_error: Some(1) };
+    let mut messages = FixStreamParser::new(reader);
+    assert_eq!(Some("0"), messages.next().unwrap().unwrap().get(35));
+    assert_eq!(Some(Err(ParseError::Io { message: String::from("connection reset"), offset: 40 })), messages.next().map(|message| message.map(|message| message.to_json())));
+    // the bytes read before the error are parsed anyway
+    let incomplete_message = messages.next().unwrap().unwrap();
+    assert_eq!((Some("FIX.4.4"), None), (incomplete_message.get(8), incomplete_message.get(35)));
+    assert!(messages.next().is_none());
+}
+
+#[test]
+fn log_lines_of_builtin_formats() {
+    let formats = LogFormat::builtin();
+    let lines = [
+        ("20200520-19:15:45.134: 8=FIX.4.4|35=0|10=209", "QuickFIX/J", "20200520-19:15:45.134", None),
+        ("20200520-19:15:45.134 : 8=FIX.4.4|35=0|10=209", "QuickFIX/n", "20200520-19:15:45.134", None),
+        ("20200520-19:15:45.134 IN  8=FIX.4.4|35=0|10=209", "OnixS", "20200520-19:15:45.134", Some(Direction::Incoming)),
+        ("20200520-19:15:45 OUT 8=FIX.4.4|35=0|10=209", "OnixS", "20200520-19:15:45", Some(Direction::Outgoing)),
+        ("2020-05-20 19:15:45.134000000 out  18 8=FIX.4.4|35=0|10=209", "Fix8", "2020-05-20 19:15:45.134000000", Some(Direction::Outgoing)),
+    ];
+    for (line, format, timestamp, direction) in lines.iter() {
+        let (prefix, fix_message) = FixMessage::from_log_line(line, &formats).unwrap();
+        assert_eq!(Some(*format), prefix.format.as_deref(), "{}", line);
+        assert_eq!(Some(*timestamp), prefix.timestamp.as_deref(), "{}", line);
+        assert_eq!(*direction, prefix.direction, "{}", line);
+        assert_eq!(None, prefix.session_id);
+        assert_eq!(&line[..line.find("8=FIX").unwrap()], prefix.raw);
+        assert_eq!(r#"{"8":"FIX.4.4","35":"0","10":"209"}"#, fix_message.to_json().to_string());
+        assert!(fix_message.warnings().is_empty(), "{}", line);
+    }
+}
+
+#[test]
+fn log_lines_of_user_format() {
+    let format = LogFormat::new("engine", r"^(?P<timestamp>\S+ \S+) \[(?P<session_id>[^\]]+)\] (?P<direction><<|>>) $").unwrap();
+    assert_eq!("engine", format.name());
+    let formats = [format, LogFormat::onixs()];
+
+    let (prefix, fix_message) = FixMessage::from_log_line("2020-05-20 19:15:45 [FIX.4.4:A->B] << 8=FIX.4.4|35=A|10=209", &formats).unwrap();
+    assert_eq!(LogPrefix { raw: String::from("2020-05-20 19:15:45 [FIX.4.4:A->B] << "), format: Some(String::from("engine")), timestamp: Some(String::from("2020-05-20 19:15:45")), direction: Some(Direction::Incoming), session_id: Some(String::from("FIX.4.4:A->B")) }, prefix);
+    assert_eq!(Some("A"), fix_message.get(35));
+
+    // the first format which matches recognizes the prefix
+    let (prefix, _) = FixMessage::from_log_line("20200520-19:15:45.134 OUT 8=FIX.4.4|35=A|10=209", &formats).unwrap();
+    assert_eq!((Some("OnixS"), Some(Direction::Outgoing)), (prefix.format.as_deref(), prefix.direction));
+
+    // eg: engines which log the direction and the session after the timestamp (not a built-in format)
+    let line = "20200520-19:15:45.134 Recv FIX.4.4:A->B 8=FIX.4.4|9=5|35=0|10=163";
+    assert_eq!(None, FixMessage::from_log_line(line, &LogFormat::builtin()).unwrap().0.format);
+    let format = LogFormat::new("engine", r"^(?P<timestamp>\S+) (?P<direction>Recv|Sent) (?P<session_id>\S+) $").unwrap();
+    let (prefix, fix_message) = FixMessage::from_log_line(line, &[format]).unwrap();
+    assert_eq!(LogPrefix { raw: String::from("20200520-19:15:45.134 Recv FIX.4.4:A->B "), format: Some(String::from("engine")), timestamp: Some(String::from("20200520-19:15:45.134")), direction: Some(Direction::Incoming), session_id: Some(String::from("FIX.4.4:A->B")) }, prefix);
+    assert_eq!(r#"{"8":"FIX.4.4","9":"5","35":"0","10":"163"}"#, fix_message.to_json().to_string());
+
+    // unknown directions are ignored
+    let format = LogFormat::new("engine", r"^(?P<direction>\w+): $").unwrap();
+    let (prefix, _) = FixMessage::from_log_line("Dropped: 8=FIX.4.4|35=A|10=209", &[format]).unwrap();
+    assert_eq!((Some("engine"), None), (prefix.format.as_deref(), prefix.direction));
+}
+
+#[test]
+fn log_lines_not_recognized() {
+    let (prefix, fix_message) = FixMessage::from_log_line("Recv | 8=FIX.4.4 | 35=0 | 10=209", &LogFormat::builtin()).unwrap();
+    assert_eq!(LogPrefix { raw: String::from("Recv | "), ..LogPrefix::default() }, prefix);
+    assert_eq!(Some("0"), fix_message.get(35));
+
+    let (prefix, _) = FixMessage::from_log_line("8=FIX.4.4|35=0|10=209", &LogFormat::builtin()).unwrap();
+    assert_eq!(LogPrefix::default(), prefix);
+
+    // the message begins at the first 8=FIX which is not the end of another field (as in streams)
+    let line = "Recv 18=x 8=FIX.4.4 | 35=0 | 10=209";
+    let (prefix, fix_message) = FixMessage::from_log_line(line, &LogFormat::builtin()).unwrap();
+    assert_eq!(LogPrefix { raw: String::from("Recv 18=x "), ..LogPrefix::default() }, prefix);
+    assert_eq!(Some("0"), fix_message.get(35));
+    let messages = FixStreamParser::new(line.as_bytes()).collect::<Result<Vec<_>, _>>().unwrap();
+    assert_eq!(fix_message.to_json(), messages[0].to_json());
+
+    assert!(matches!(FixMessage::from_log_line("20200520-19:15:45.134 IN  35=0|10=209", &LogFormat::builtin()), Err(ParseError::MissingBeginString { .. })));
+}
+
+#[test]
+fn log_lines_with_options() {
+    let line = "20200520-19:15:45.134 OUT 8=FIX.4.4|9=5|35=0|10=163";
+    let options = ParserOptions::new().verify_checksum(true);
+    let (prefix, fix_message) = FixMessage::from_log_line_with(line, &LogFormat::builtin(), &options).unwrap();
+    assert_eq!(Some(Direction::Outgoing), prefix.direction);
+    assert!(fix_message.checksum().unwrap().is_valid());
+
+    // the offsets are relative to the line
+    let error = FixMessage::from_log_line_with("20200520-19:15:45.134 OUT 8=FIX.4.4|9=5|35=0|", &LogFormat::builtin(), &ParserOptions::strict()).err();
+    assert!(matches!(error, Some(ParseError::Rejected { ref warning }) if warning.kind == WarningKind::MissingChecksum && warning.offset == 45));
+}
+
+#[test]
+fn invalid_log_formats() {
+    assert_eq!(LogFormatError::UnknownCapture { name: String::from("seq_num") }, LogFormat::new("engine", r"^(?P<timestamp>\S+) (?P<seq_num>\d+) $").unwrap_err());
+    assert!(matches!(LogFormat::new("engine", r"^(?P<timestamp>\S+ $"), Err(LogFormatError::InvalidRegex { .. })));
+}
+
+// header, body and trailer
+
+#[test]
+fn header_body_and_trailer() {
+    let input = "8=FIX.4.4 | 9=70 | 35=D | 49=A | 56=B | 34=12 | 627=2 | 628=H1 | 628=H2 | 52=20200520-19:15:45 | 11=order | 453=2 | 448=P | 448=Q | 58=text | 93=2 | 89=XX | 10=209";
+    let fix_message = FixMessage::from_tag_value(input).unwrap();
+    assert_eq!(vec![8, 9, 35, 49, 56, 34, 627, 52], fix_message.header().entities().map(|entity| entity.tag()).collect::<Vec<_>>());
+    assert_eq!(vec![11, 453, 58], fix_message.body().entities().map(|entity| entity.tag()).collect::<Vec<_>>());
+    assert_eq!(vec![93, 89, 10], fix_message.trailer().entities().map(|entity| entity.tag()).collect::<Vec<_>>());
+    assert_eq!(Some("H2"), fix_message.header().group(627).unwrap().instance(1).unwrap().get(628));
+    assert_eq!(vec!["P", "Q"], fix_message.body().query(&"453[*].448".parse().unwrap()));
+    assert_eq!(None, fix_message.body().get(35));
+
+    let output = r#"{"Header":{"8":"FIX.4.4","9":"70","35":"D","49":"A","56":"B","34":"12","627":[{"628":"H1"},{"628":"H2"}],"52":"20200520-19:15:45"},"Body":{"11":"order","453":[{"448":"P"},{"448":"Q"}],"58":"text"},"Trailer":{"93":"2","89":"XX","10":"209"}}"#;
+    assert_eq!(output, fix_message.to_json_with_sections().to_string());
+    assert_eq!(output, FixMessageRef::from_tag_value(input).unwrap().to_json_with_sections().to_string());
+    assert_eq!(r#"{"11":"order","453":[{"448":"P"},{"448":"Q"}],"58":"text"}"#, serde_json::to_string(&fix_message.body()).unwrap());
+
+    let fix_message_ref = FixMessageRef::from_tag_value(input).unwrap();
+    assert_eq!((Some("12"), Some("text"), Some("XX")), (fix_message_ref.header().get(34), fix_message_ref.body().get(58), fix_message_ref.trailer().get(89)));
+}
+
+#[test]
+fn header_and_trailer_tags_out_of_place() {
+    // header tags after the first field of the body, and trailer tags before the last field of the body, are part of the body
+    let input = "8=FIX.4.4 | 35=D | 11=order | 52=20200520-19:15:45 | 89=XX | 58=text | 10=209";
+    let fix_message = FixMessage::from_tag_value(input).unwrap();
+    assert_eq!(r#"{"Header":{"8":"FIX.4.4","35":"D"},"Body":{"11":"order","52":"20200520-19:15:45","89":"XX","58":"text"},"Trailer":{"10":"209"}}"#, fix_message.to_json_with_sections().to_string());
+
+    // messages without body or trailer
+    let fix_message = FixMessage::from_tag_value("8=FIX.4.4 | 9=5 | 35=0 | 10=209").unwrap();
+    assert_eq!(r#"{"Header":{"8":"FIX.4.4","9":"5","35":"0"},"Body":{},"Trailer":{"10":"209"}}"#, fix_message.to_json_with_sections().to_string());
+    let fix_message = FixMessage::from_tag_value("8=FIX.4.4 | 9=5 | 35=0 | 58=text").unwrap();
+    assert_eq!(r#"{"Header":{"8":"FIX.4.4","9":"5","35":"0"},"Body":{"58":"text"},"Trailer":{}}"#, fix_message.to_json_with_sections().to_string());
+}